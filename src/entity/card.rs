@@ -1,27 +1,52 @@
 use std::cmp::Ordering;
-use std::fmt::{Debug, format, Formatter};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
-use std::rc::Rc;
-use Type::*;
+use std::str::FromStr;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
+use crate::entity::card_set::CardSet;
+use crate::entity::ruleset::Ruleset;
+use Type::*;
 
 #[derive(EnumIter, Debug, PartialEq, Eq, Hash, Copy, Clone)]
 pub enum Type {
     SPADE, CLUB, HEART, DIAMOND
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
 pub struct SolitaireCard {
     card_type : Type,
     number : i16
 }
 
+//PokerCard compares, orders and hashes by number only, which the evaluator relies on to group and sort
+//by rank; it is not a card identity, duplicate checks go through Card and CardSet
 #[derive(Eq)]
 pub struct PokerCard {
     card : SolitaireCard
 }
 
+//rank and suit packed as (poker number - 2) * 4 + suit, 0 is the 2 of spades and 51 the ace of diamonds;
+//unlike PokerCard it compares and hashes the suit too, jokers have no index
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub struct Card {
+    index : u8
+}
+
+//a list of cards written in two-character notation, e.g. "As Kd 7c"
+pub struct PokerDeck {
+    cards : Vec<PokerCard>
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseCardError {
+    InvalidLength(String),
+    InvalidRank(char),
+    InvalidSuit(char),
+    DuplicateCard(String)
+}
+
 impl SolitaireCard {
     pub fn new(card_type : Type, number : i16) -> Self {
         SolitaireCard{
@@ -40,6 +65,11 @@ impl SolitaireCard {
         result
     }
 
+    //a joker is number 0, the suit only tells the black (spade) and red (heart) joker apart
+    pub fn new_joker(card_type : Type) -> Self {
+        SolitaireCard::new(card_type, 0)
+    }
+
     pub fn get_card_type(&self) -> Type {
         self.card_type
     }
@@ -47,6 +77,10 @@ impl SolitaireCard {
     pub fn get_number(&self) -> i16 {
         self.number
     }
+
+    pub fn is_joker(&self) -> bool {
+        self.number == 0
+    }
 }
 
 impl PokerCard {
@@ -62,6 +96,10 @@ impl PokerCard {
         }
     }
 
+    pub fn new_joker(card_type : Type) -> Self {
+        Self::new_by_solitaire_card(SolitaireCard::new_joker(card_type))
+    }
+
     pub fn get_all_card() -> Vec<PokerCard>{
         let mut result:Vec<PokerCard> = Vec::new();
         for card in SolitaireCard::get_all_card() {
@@ -70,6 +108,10 @@ impl PokerCard {
         result
     }
 
+    pub fn get_all_card_by_ruleset(ruleset : Ruleset) -> Vec<PokerCard>{
+        PokerCard::get_all_card().into_iter().filter(|card| ruleset.is_in_deck(card.get_number())).collect()
+    }
+
     pub fn get_card_type(&self) -> Type {
         self.card.card_type
     }
@@ -78,9 +120,96 @@ impl PokerCard {
         self.card.number
     }
 
+    pub fn is_joker(&self) -> bool {
+        self.card.is_joker()
+    }
+
+    //a poker card numbers the ace 1, so 14 is as invalid here as a joker
+    pub fn get_card(&self) -> Option<Card> {
+        if (1..=13).contains(&self.get_number()) { Card::new(self.get_card_type(), self.get_number()) } else { None }
+    }
+
+    //ace ranks above king when comparing poker hands
+    pub fn get_poker_number(&self) -> i16 {
+        if self.card.number == 1 { 14 } else { self.card.number }
+    }
+
+    //PartialEq only compares numbers, this also compares the suit
+    pub fn is_same_card(&self, other : &PokerCard) -> bool {
+        self.get_card_type() == other.get_card_type() && self.get_poker_number() == other.get_poker_number()
+    }
+
     pub fn get_copy(&self) -> Self {
         Self::new_by_attribute(self.get_card_type(), self.get_number())
     }
+
+    pub fn get_rank_char(&self) -> char {
+        match self.get_number() {
+            1 | 14 => 'A',
+            13 => 'K',
+            12 => 'Q',
+            11 => 'J',
+            10 => 'T',
+            0 => 'X',
+            x => char::from(b'0' + x as u8)
+        }
+    }
+
+    pub fn number_from_char(c : char) -> Option<i16> {
+        match c {
+            'A' => Some(1),
+            'K' => Some(13),
+            'Q' => Some(12),
+            'J' => Some(11),
+            'T' => Some(10),
+            'X' => Some(0),
+            '2'..='9' => Some(c as i16 - '0' as i16),
+            _ => None
+        }
+    }
+}
+
+impl Card {
+    //the ace may be given as 1 or 14
+    pub fn new(card_type : Type, number : i16) -> Option<Self> {
+        let poker_number = if number == 1 { 14 } else { number };
+        if !(2..=14).contains(&poker_number) {
+            return None;
+        }
+        Some(Card {
+            index : (poker_number - 2) as u8 * 4 + card_type as u8
+        })
+    }
+
+    pub fn new_by_index(index : u8) -> Option<Self> {
+        if index < 52 { Some(Card { index }) } else { None }
+    }
+
+    pub fn get_index(&self) -> u8 {
+        self.index
+    }
+
+    pub fn get_card_type(&self) -> Type {
+        Type::iter().nth((self.index % 4) as usize).unwrap()
+    }
+
+    pub fn get_poker_number(&self) -> i16 {
+        (self.index / 4) as i16 + 2
+    }
+
+    pub fn get_number(&self) -> i16 {
+        if self.get_poker_number() == 14 { 1 } else { self.get_poker_number() }
+    }
+
+    pub fn to_poker_card(&self) -> PokerCard {
+        PokerCard::new_by_attribute(self.get_card_type(), self.get_number())
+    }
+}
+
+impl Display for Card {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_poker_card())
+    }
 }
 
 impl PartialEq for PokerCard {
@@ -110,6 +239,7 @@ impl Hash for PokerCard {
 impl Debug for PokerCard {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let number = match self.get_number() {
+            0 => String::from("Joker"),
             1 | 14 => String::from("A"),
             11 => String::from("J"),
             12 => String::from("Q"),
@@ -120,17 +250,105 @@ impl Debug for PokerCard {
     }
 }
 
-impl ToString for PokerCard {
-    fn to_string(&self) -> String {
-        let number = match self.get_number() {
-            1 | 14 => String::from("A"),
-            11 => String::from("J"),
-            12 => String::from("Q"),
-            13 => String::from("K"),
-            x => x.to_string()
-        };
-        format!("{{{:?}, {:?}}}", number, self.get_card_type())
+impl Type {
+    pub fn get_char(&self) -> char {
+        match self {
+            SPADE => 's',
+            HEART => 'h',
+            DIAMOND => 'd',
+            CLUB => 'c'
+        }
+    }
+
+    pub fn from_char(c : char) -> Option<Type> {
+        match c {
+            's' => Some(SPADE),
+            'h' => Some(HEART),
+            'd' => Some(DIAMOND),
+            'c' => Some(CLUB),
+            _ => None
+        }
+    }
+}
+
+impl Display for PokerCard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.get_rank_char(), self.get_card_type().get_char())
+    }
+}
+
+impl FromStr for PokerCard {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars = s.chars().collect::<Vec<_>>();
+        if chars.len() != 2 {
+            return Err(ParseCardError::InvalidLength(s.to_string()));
+        }
+        let number = PokerCard::number_from_char(chars[0]).ok_or(ParseCardError::InvalidRank(chars[0]))?;
+        let card_type = Type::from_char(chars[1]).ok_or(ParseCardError::InvalidSuit(chars[1]))?;
+        Ok(PokerCard::new_by_attribute(card_type, number))
+    }
+}
+
+impl PokerDeck {
+    pub fn new(cards : Vec<PokerCard>) -> Self {
+        PokerDeck {
+            cards
+        }
+    }
+
+    pub fn get_cards(&self) -> &Vec<PokerCard> {
+        &self.cards
+    }
+
+    pub fn into_cards(self) -> Vec<PokerCard> {
+        self.cards
+    }
+}
+
+impl Display for PokerDeck {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let cards = self.cards.iter().map(|card| card.to_string()).collect::<Vec<_>>();
+        f.write_str(&cards.join(" "))
+    }
+}
+
+//cards may be separated by spaces or commas, or written back to back ("AsKd"); jokers may repeat, other cards may not
+impl FromStr for PokerDeck {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cards : Vec<PokerCard> = Vec::new();
+        let mut seen = CardSet::new();
+        for token in s.split(|c : char| c.is_whitespace() || c == ',').filter(|token| !token.is_empty()) {
+            let chars = token.chars().collect::<Vec<_>>();
+            if chars.len() % 2 != 0 {
+                return Err(ParseCardError::InvalidLength(token.to_string()));
+            }
+            for pair in chars.chunks(2) {
+                let card = pair.iter().collect::<String>().parse::<PokerCard>()?;
+                if card.get_card().is_some_and(|identity| !seen.insert(identity)) {
+                    return Err(ParseCardError::DuplicateCard(card.to_string()));
+                }
+                cards.push(card);
+            }
+        }
+        Ok(PokerDeck::new(cards))
     }
 }
 
+impl Display for ParseCardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseCardError::InvalidLength(token) => write!(f, "Invalid card notation \"{}\"", token),
+            ParseCardError::InvalidRank(c) => write!(f, "Invalid card rank '{}'", c),
+            ParseCardError::InvalidSuit(c) => write!(f, "Invalid card suit '{}'", c),
+            ParseCardError::DuplicateCard(card) => write!(f, "Duplicate card {}", card)
+        }
+    }
+}
+
+impl Error for ParseCardError {}
+
 
@@ -0,0 +1,118 @@
+use std::ops::Deref;
+use crate::entity::card::{Card, PokerCard};
+use crate::error::PokerError;
+
+const FULL : u64 = (1 << 52) - 1;
+
+//one bit per Card index
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default)]
+pub struct CardSet {
+    bits : u64
+}
+
+impl CardSet {
+    pub fn new() -> Self {
+        CardSet {
+            bits : 0
+        }
+    }
+
+    pub fn new_full() -> Self {
+        CardSet {
+            bits : FULL
+        }
+    }
+
+    //fails on the first joker or repeated card
+    pub fn new_by_cards<C : Deref<Target = PokerCard>>(cards : &[C]) -> Result<Self, PokerError> {
+        let mut result = CardSet::new();
+        for card in cards.iter() {
+            let identity = card.get_card().ok_or_else(|| PokerError::InvalidCard(card.to_string()))?;
+            if !result.insert(identity) {
+                return Err(PokerError::DuplicateCard(card.to_string()));
+            }
+        }
+        Ok(result)
+    }
+
+    pub fn get_bits(&self) -> u64 {
+        self.bits
+    }
+
+    //false when the card was already in the set
+    pub fn insert(&mut self, card : Card) -> bool {
+        let was_new = !self.contains(card);
+        self.bits |= 1 << card.get_index();
+        was_new
+    }
+
+    //false when the card was not in the set
+    pub fn remove(&mut self, card : Card) -> bool {
+        let was_in = self.contains(card);
+        self.bits &= !(1 << card.get_index());
+        was_in
+    }
+
+    pub fn contains(&self, card : Card) -> bool {
+        self.bits & (1 << card.get_index()) != 0
+    }
+
+    pub fn union(&self, other : &CardSet) -> CardSet {
+        CardSet {
+            bits : self.bits | other.bits
+        }
+    }
+
+    pub fn intersection(&self, other : &CardSet) -> CardSet {
+        CardSet {
+            bits : self.bits & other.bits
+        }
+    }
+
+    pub fn difference(&self, other : &CardSet) -> CardSet {
+        CardSet {
+            bits : self.bits & !other.bits
+        }
+    }
+
+    pub fn is_disjoint(&self, other : &CardSet) -> bool {
+        self.bits & other.bits == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    //from the lowest index up
+    pub fn iter(&self) -> impl Iterator<Item = Card> {
+        let mut bits = self.bits;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let index = bits.trailing_zeros() as u8;
+            bits &= bits - 1;
+            Card::new_by_index(index)
+        })
+    }
+}
+
+impl Extend<Card> for CardSet {
+    fn extend<T : IntoIterator<Item = Card>>(&mut self, iter : T) {
+        for card in iter {
+            self.insert(card);
+        }
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<T : IntoIterator<Item = Card>>(iter : T) -> Self {
+        let mut result = CardSet::new();
+        result.extend(iter);
+        result
+    }
+}
@@ -0,0 +1,266 @@
+use std::ops::Deref;
+use rand::seq::SliceRandom;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use strum::IntoEnumIterator;
+use crate::entity::card::{PokerCard, SolitaireCard, Type};
+use crate::entity::ruleset::Ruleset;
+use crate::error::PokerError;
+
+//any card type a deck can be built from
+pub trait DeckCard : Sized {
+    fn new_by_solitaire_card(card : SolitaireCard) -> Self;
+
+    fn get_solitaire_card(&self) -> SolitaireCard;
+
+    //None when the card type cannot hold a joker, the index alternates between the black and the red joker
+    fn new_joker(_index : usize) -> Option<Self> {
+        None
+    }
+
+    fn get_copy(&self) -> Self {
+        Self::new_by_solitaire_card(self.get_solitaire_card())
+    }
+
+    //rank and suit, not only the number
+    fn is_same_card(&self, other : &Self) -> bool {
+        self.get_solitaire_card() == other.get_solitaire_card()
+    }
+}
+
+//how many 52 card decks go in and which numbers each of them keeps
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DeckSpec {
+    decks : usize,
+    numbers : Vec<i16>
+}
+
+//cards are dealt from the back of the stub; burned and discarded cards wait in the muck
+pub struct Deck<C> {
+    spec : DeckSpec,
+    jokers : usize,
+    cards : Vec<C>,
+    muck : Vec<C>,
+    cut_card : usize,
+    rng : Box<dyn RngCore>,
+    seed : Option<u64>
+}
+
+impl Default for DeckSpec {
+    fn default() -> Self {
+        Self::new_with_decks(1)
+    }
+}
+
+impl DeckSpec {
+    pub fn new(decks : usize, numbers : &[i16]) -> Result<Self, PokerError> {
+        let mut sorted = numbers.to_vec();
+        sorted.sort();
+        sorted.dedup();
+        if sorted.len() != numbers.len() || sorted.iter().any(|number| !(1..=13).contains(number)) {
+            return Err(PokerError::InvalidDeck);
+        }
+        Ok(DeckSpec {
+            decks,
+            numbers : sorted
+        })
+    }
+
+    pub fn new_with_decks(decks : usize) -> Self {
+        DeckSpec {
+            decks,
+            numbers : (1..=13).collect()
+        }
+    }
+
+    pub fn new_by_ruleset(ruleset : Ruleset) -> Self {
+        DeckSpec {
+            decks : 1,
+            numbers : (1..=13).filter(|&number| ruleset.is_in_deck(number)).collect()
+        }
+    }
+
+    pub fn get_decks(&self) -> usize {
+        self.decks
+    }
+
+    pub fn get_numbers(&self) -> &Vec<i16> {
+        &self.numbers
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.decks * self.numbers.len() * Type::iter().count()
+    }
+
+    fn build<C : DeckCard>(&self) -> Vec<C> {
+        let mut result = Vec::new();
+        for _ in 0..self.decks {
+            for card_type in Type::iter() {
+                for &number in self.numbers.iter() {
+                    result.push(C::new_by_solitaire_card(SolitaireCard::new(card_type, number)));
+                }
+            }
+        }
+        result
+    }
+}
+
+impl<C : DeckCard> Deck<C> {
+    //ChaCha8 keeps the same stream across rand versions, so a stored seed always replays the same deal
+    pub fn new_with_seed(spec : DeckSpec, seed : u64) -> Self {
+        let mut deck = Self::new_with_rng(spec, ChaCha8Rng::seed_from_u64(seed));
+        deck.seed = Some(seed);
+        deck
+    }
+
+    pub fn new_with_rng(spec : DeckSpec, rng : impl RngCore + 'static) -> Self {
+        let mut deck = Deck {
+            spec,
+            jokers : 0,
+            cards : Vec::new(),
+            muck : Vec::new(),
+            cut_card : 0,
+            rng : Box::new(rng),
+            seed : None
+        };
+        deck.shuffle();
+        deck
+    }
+
+    pub fn new_with_jokers(spec : DeckSpec, jokers : usize, seed : u64) -> Result<Self, PokerError> {
+        if jokers > 0 && C::new_joker(0).is_none() {
+            return Err(PokerError::InvalidCard(String::from("joker")));
+        }
+        let mut deck = Self::new_with_seed(spec, seed);
+        deck.jokers = jokers;
+        deck.shuffle();
+        Ok(deck)
+    }
+
+    //a shoe gets reshuffled between rounds once no more than cut_card cards are left
+    pub fn new_shoe_with_seed(spec : DeckSpec, cut_card : usize, seed : u64) -> Self {
+        let mut deck = Self::new_with_seed(spec, seed);
+        deck.cut_card = cut_card;
+        deck
+    }
+
+    pub fn get_spec(&self) -> &DeckSpec {
+        &self.spec
+    }
+
+    pub fn get_jokers(&self) -> usize {
+        self.jokers
+    }
+
+    pub fn get_seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn get_cards(&self) -> &Vec<C> {
+        &self.cards
+    }
+
+    pub fn get_muck(&self) -> &Vec<C> {
+        &self.muck
+    }
+
+    pub fn get_remaining(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn get_cut_card(&self) -> usize {
+        self.cut_card
+    }
+
+    pub fn is_cut_card_reached(&self) -> bool {
+        self.cards.len() <= self.cut_card
+    }
+
+    //collects every card back, dealt ones included, and shuffles the full deck
+    pub fn shuffle(&mut self) {
+        self.cards = self.spec.build();
+        self.cards.extend((0..self.jokers).filter_map(C::new_joker));
+        self.muck = Vec::new();
+        self.cards.shuffle(&mut self.rng);
+    }
+
+    //the next cards in the order they will be dealt
+    pub fn peek(&self, number : usize) -> Vec<&C> {
+        self.cards.iter().rev().take(number).collect()
+    }
+
+    pub fn burn(&mut self) -> Result<(), PokerError> {
+        let card = self.draw_one()?;
+        self.muck.push(card);
+        Ok(())
+    }
+
+    pub fn discard<R : Deref<Target = C>>(&mut self, cards : &[R]) {
+        for card in cards.iter() {
+            self.muck.push(card.get_copy());
+        }
+    }
+
+    //takes already known cards (other hands, board, dead cards) out of the stub, one copy each
+    pub fn remove_cards<R : Deref<Target = C>>(&mut self, cards : &[R]) -> Result<(), PokerError> {
+        for card in cards.iter() {
+            match self.cards.iter().position(|other| other.is_same_card(card)) {
+                Some(index) => { self.cards.remove(index); }
+                None => return Err(PokerError::CardNotInDeck(PokerCard::new_by_solitaire_card(card.get_solitaire_card()).to_string()))
+            }
+        }
+        Ok(())
+    }
+
+    pub fn draw_one(&mut self) -> Result<C, PokerError> {
+        if self.cards.is_empty() {
+            self.cards.append(&mut self.muck);
+            self.cards.shuffle(&mut self.rng);
+        }
+        self.cards.pop().ok_or(PokerError::DeckExhausted)
+    }
+
+    //when the stub runs out the muck is shuffled back in
+    pub fn draw(&mut self, number : usize) -> Result<Vec<C>, PokerError> {
+        if number > self.cards.len() + self.muck.len() {
+            return Err(PokerError::DeckExhausted);
+        }
+        let mut result = Vec::new();
+        for _ in 0..number {
+            result.push(self.draw_one()?);
+        }
+        Ok(result)
+    }
+}
+
+impl DeckCard for SolitaireCard {
+    fn new_by_solitaire_card(card : SolitaireCard) -> Self {
+        card
+    }
+
+    fn get_solitaire_card(&self) -> SolitaireCard {
+        *self
+    }
+
+    fn new_joker(index : usize) -> Option<Self> {
+        Some(SolitaireCard::new_joker(joker_type(index)))
+    }
+}
+
+impl DeckCard for PokerCard {
+    fn new_by_solitaire_card(card : SolitaireCard) -> Self {
+        PokerCard::new_by_solitaire_card(card)
+    }
+
+    fn get_solitaire_card(&self) -> SolitaireCard {
+        SolitaireCard::new(self.get_card_type(), self.get_number())
+    }
+
+    fn new_joker(index : usize) -> Option<Self> {
+        Some(PokerCard::new_joker(joker_type(index)))
+    }
+}
+
+fn joker_type(index : usize) -> Type {
+    if index.is_multiple_of(2) { Type::SPADE } else { Type::HEART }
+}
@@ -0,0 +1,97 @@
+use std::cmp::Ordering;
+use std::rc::Rc;
+use strum_macros::{Display, EnumIter};
+use crate::entity::card::PokerCard;
+use crate::entity::ruleset::Ruleset;
+
+#[derive(Display, EnumIter, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub enum HandCategory {
+    HighCard,
+    Pair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+    RoyalFlush,
+    //only with wild cards
+    FiveOfAKind
+}
+
+//one more card completes a flush draw, an open-ended straight draw (two numbers, double gutshots included)
+//or a gutshot (one number); backdoor draws need both the turn and the river, a combo draw is a flush draw
+//together with a straight draw
+#[derive(Display, EnumIter, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub enum Draw {
+    FlushDraw,
+    OpenEnded,
+    Gutshot,
+    BackdoorFlush,
+    BackdoorStraight,
+    ComboDraw
+}
+
+//kickers are the card numbers (ace as 14) deciding ties inside the same category, most significant first;
+//the ruleset decides which category is stronger, values from different rulesets should not be compared
+#[derive(Debug, Clone)]
+pub struct HandValue {
+    category : HandCategory,
+    kickers : Vec<i16>,
+    chosen : [Rc<PokerCard>; 5],
+    ruleset : Ruleset
+}
+
+impl HandValue {
+    pub fn new(category : HandCategory, kickers : Vec<i16>, chosen : [Rc<PokerCard>; 5]) -> Self {
+        Self::new_with_ruleset(category, kickers, chosen, Ruleset::Standard)
+    }
+
+    pub fn new_with_ruleset(category : HandCategory, kickers : Vec<i16>, chosen : [Rc<PokerCard>; 5], ruleset : Ruleset) -> Self {
+        HandValue {
+            category,
+            kickers,
+            chosen,
+            ruleset
+        }
+    }
+
+    pub fn get_category(&self) -> HandCategory {
+        self.category
+    }
+
+    pub fn get_kickers(&self) -> &Vec<i16> {
+        &self.kickers
+    }
+
+    pub fn get_chosen(&self) -> &[Rc<PokerCard>; 5] {
+        &self.chosen
+    }
+
+    pub fn get_ruleset(&self) -> Ruleset {
+        self.ruleset
+    }
+}
+
+impl PartialEq for HandValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for HandValue {}
+
+impl Ord for HandValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.ruleset.get_category_strength(self.category)
+            .cmp(&other.ruleset.get_category_strength(other.category))
+            .then_with(|| self.kickers.cmp(&other.kickers))
+    }
+}
+
+impl PartialOrd for HandValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -1 +1,6 @@
-pub mod card;
\ No newline at end of file
+pub mod card;
+pub mod card_set;
+pub mod deck;
+pub mod hand;
+pub mod range;
+pub mod ruleset;
\ No newline at end of file
@@ -0,0 +1,223 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::Deref;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use crate::entity::card::{PokerCard, PokerDeck, Type};
+
+pub struct RangeCombo {
+    cards : [PokerCard; 2],
+    weight : f64
+}
+
+//weighted two-card combos parsed from range syntax such as "TT+, AKs, A5s-A2s, KQo, 76s:0.5"
+#[derive(Default)]
+pub struct HandRange {
+    combos : Vec<RangeCombo>
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseRangeError {
+    InvalidHand(String),
+    InvalidSpan(String),
+    InvalidWeight(String)
+}
+
+//numbers are poker numbers (ace as 14), suited is None when both suited and offsuit combos are wanted
+#[derive(Clone, Copy)]
+struct HandClass {
+    high : i16,
+    low : i16,
+    suited : Option<bool>
+}
+
+impl RangeCombo {
+    pub fn get_cards(&self) -> &[PokerCard; 2] {
+        &self.cards
+    }
+
+    pub fn get_weight(&self) -> f64 {
+        self.weight
+    }
+
+    pub fn is_blocked_by<C: Deref<Target = PokerCard>>(&self, known : &[C]) -> bool {
+        known.iter().any(|card| self.cards.iter().any(|own| own.is_same_card(card)))
+    }
+
+    fn is_same_combo(&self, other : &RangeCombo) -> bool {
+        other.cards.iter().all(|card| self.cards.iter().any(|own| own.is_same_card(card)))
+    }
+}
+
+impl HandRange {
+    pub fn new() -> Self {
+        HandRange {
+            combos : Vec::new()
+        }
+    }
+
+    //a single known hand, so hand-vs-range equity can go through the same calculator
+    pub fn from_cards(first : &PokerCard, second : &PokerCard) -> Self {
+        let mut range = HandRange::new();
+        range.add_combo(first.get_copy(), second.get_copy(), 1.0);
+        range
+    }
+
+    pub fn get_combos(&self) -> &Vec<RangeCombo> {
+        &self.combos
+    }
+
+    pub fn get_total_weight(&self) -> f64 {
+        self.combos.iter().map(|combo| combo.weight).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    pub fn remove_blocked<C: Deref<Target = PokerCard>>(&mut self, known : &[C]) {
+        self.combos.retain(|combo| !combo.is_blocked_by(known));
+    }
+
+    //a combo listed twice keeps the last weight
+    fn add_combo(&mut self, first : PokerCard, second : PokerCard, weight : f64) {
+        let combo = RangeCombo {
+            cards : [first, second],
+            weight
+        };
+        self.combos.retain(|other| !other.is_same_combo(&combo));
+        if weight > 0.0 {
+            self.combos.push(combo);
+        }
+    }
+
+    fn add_class(&mut self, class : HandClass, weight : f64) {
+        for first_type in Type::iter() {
+            for second_type in Type::iter() {
+                let suited = first_type == second_type;
+                if class.high == class.low {
+                    if (first_type as u8) >= (second_type as u8) {
+                        continue;
+                    }
+                } else if class.suited.is_some_and(|wanted| wanted != suited) {
+                    continue;
+                }
+                self.add_combo(to_card(first_type, class.high), to_card(second_type, class.low), weight);
+            }
+        }
+    }
+}
+
+impl FromStr for HandRange {
+    type Err = ParseRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut range = HandRange::new();
+        for token in s.split(',').map(|token| token.trim()).filter(|token| !token.is_empty()) {
+            let (hand, weight) = match token.split_once(':') {
+                Some((hand, weight)) => {
+                    let weight = weight.trim().parse::<f64>()
+                        .ok()
+                        .filter(|weight| (0.0..=1.0).contains(weight))
+                        .ok_or(ParseRangeError::InvalidWeight(token.to_string()))?;
+                    (hand.trim(), weight)
+                }
+                None => (token, 1.0)
+            };
+            parse_hand(&mut range, hand, weight)?;
+        }
+        Ok(range)
+    }
+}
+
+impl Display for ParseRangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseRangeError::InvalidHand(token) => write!(f, "Invalid range hand \"{}\"", token),
+            ParseRangeError::InvalidSpan(token) => write!(f, "Invalid range span \"{}\"", token),
+            ParseRangeError::InvalidWeight(token) => write!(f, "Invalid range weight \"{}\"", token)
+        }
+    }
+}
+
+impl Error for ParseRangeError {}
+
+fn parse_hand(range : &mut HandRange, hand : &str, weight : f64) -> Result<(), ParseRangeError> {
+    let invalid_span = || ParseRangeError::InvalidSpan(hand.to_string());
+    if let Some((top, bottom)) = hand.split_once('-') {
+        let top = parse_class(top)?;
+        let bottom = parse_class(bottom)?;
+        if top.suited != bottom.suited {
+            return Err(invalid_span());
+        }
+        if top.high == top.low && bottom.high == bottom.low {
+            for number in bottom.high.min(top.high)..=bottom.high.max(top.high) {
+                range.add_class(HandClass { high : number, low : number, suited : None }, weight);
+            }
+        } else if top.high == bottom.high && top.high != top.low && bottom.high != bottom.low {
+            for low in bottom.low.min(top.low)..=bottom.low.max(top.low) {
+                range.add_class(HandClass { low, ..top }, weight);
+            }
+        } else {
+            return Err(invalid_span());
+        }
+    } else if let Some(base) = hand.strip_suffix('+') {
+        let class = parse_class(base)?;
+        if class.high == class.low {
+            for number in class.high..=14 {
+                range.add_class(HandClass { high : number, low : number, suited : None }, weight);
+            }
+        } else {
+            for low in class.low..class.high {
+                range.add_class(HandClass { low, ..class }, weight);
+            }
+        }
+    } else if hand.chars().count() == 4 {
+        let cards = hand.parse::<PokerDeck>().map_err(|_| ParseRangeError::InvalidHand(hand.to_string()))?.into_cards();
+        if cards.iter().any(PokerCard::is_joker) {
+            return Err(ParseRangeError::InvalidHand(hand.to_string()));
+        }
+        let mut cards = cards.into_iter();
+        range.add_combo(cards.next().unwrap(), cards.next().unwrap(), weight);
+    } else {
+        range.add_class(parse_class(hand)?, weight);
+    }
+    Ok(())
+}
+
+fn parse_class(hand : &str) -> Result<HandClass, ParseRangeError> {
+    let invalid = || ParseRangeError::InvalidHand(hand.to_string());
+    let chars = hand.trim().chars().collect::<Vec<_>>();
+    if chars.len() != 2 && chars.len() != 3 {
+        return Err(invalid());
+    }
+    let first = rank_from_char(chars[0]).ok_or_else(invalid)?;
+    let second = rank_from_char(chars[1]).ok_or_else(invalid)?;
+    let suited = match chars.get(2) {
+        None => None,
+        Some('s') => Some(true),
+        Some('o') => Some(false),
+        Some(_) => return Err(invalid())
+    };
+    if first == second && suited.is_some() {
+        return Err(invalid());
+    }
+    Ok(HandClass {
+        high : first.max(second),
+        low : first.min(second),
+        suited
+    })
+}
+
+//ranges are over real cards, so the joker rank X is not a rank here
+fn rank_from_char(c : char) -> Option<i16> {
+    PokerCard::number_from_char(c).filter(|&number| number != 0).map(to_poker_number)
+}
+
+fn to_poker_number(number : i16) -> i16 {
+    if number == 1 { 14 } else { number }
+}
+
+fn to_card(card_type : Type, poker_number : i16) -> PokerCard {
+    PokerCard::new_by_attribute(card_type, if poker_number == 14 { 1 } else { poker_number })
+}
@@ -0,0 +1,81 @@
+use crate::entity::hand::HandCategory;
+use crate::entity::hand::HandCategory::*;
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default)]
+pub enum Ruleset {
+    #[default]
+    Standard,
+    //36 cards from 6 to ace, A-6-7-8-9 is the lowest straight and a flush beats a full house
+    ShortDeck { trips_beat_straight : bool }
+}
+
+//jokers are always wild, so are the designated numbers (2 for deuces wild);
+//with the bug a joker only completes straights and flushes or plays as an ace
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct WildCards {
+    numbers : Vec<i16>,
+    bug : bool
+}
+
+impl Ruleset {
+    pub fn is_in_deck(&self, number : i16) -> bool {
+        match self {
+            Ruleset::Standard => true,
+            Ruleset::ShortDeck { .. } => number == 1 || number >= 6
+        }
+    }
+
+    //the number the ace takes at the bottom of a straight
+    pub fn get_low_ace_number(&self) -> i16 {
+        match self {
+            Ruleset::Standard => 1,
+            Ruleset::ShortDeck { .. } => 5
+        }
+    }
+
+    //bigger is stronger
+    pub fn get_category_strength(&self, category : HandCategory) -> u8 {
+        let strength = category as u8;
+        match (self, category) {
+            (Ruleset::ShortDeck { .. }, Flush) => FullHouse as u8,
+            (Ruleset::ShortDeck { .. }, FullHouse) => Flush as u8,
+            (Ruleset::ShortDeck { trips_beat_straight : true }, ThreeOfAKind) => Straight as u8,
+            (Ruleset::ShortDeck { trips_beat_straight : true }, Straight) => ThreeOfAKind as u8,
+            _ => strength
+        }
+    }
+}
+
+impl WildCards {
+    pub fn new(numbers : &[i16], bug : bool) -> Self {
+        WildCards {
+            numbers : numbers.to_vec(),
+            bug
+        }
+    }
+
+    pub fn new_bug() -> Self {
+        Self::new(&[], true)
+    }
+
+    pub fn new_deuces() -> Self {
+        Self::new(&[2], false)
+    }
+
+    pub fn get_numbers(&self) -> &Vec<i16> {
+        &self.numbers
+    }
+
+    pub fn is_bug(&self) -> bool {
+        self.bug
+    }
+
+    //0 is the joker
+    pub fn is_wild(&self, number : i16) -> bool {
+        number == 0 || self.numbers.contains(&number)
+    }
+
+    pub fn is_bug_card(&self, number : i16) -> bool {
+        self.bug && number == 0
+    }
+}
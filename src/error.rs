@@ -0,0 +1,129 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use crate::entity::card::ParseCardError;
+use crate::service::blackjack_service::BlackjackError;
+use crate::service::tien_len_service::TienLenError;
+
+//returned by every public entry point; the table games keep their own rule errors and wrap them here
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PokerError {
+    DeckExhausted,
+    InvalidCard(String),
+    ParseCard(ParseCardError),
+    DuplicateCard(String),
+    CardNotInDeck(String),
+    InvalidDeck,
+    TooFewCards { minimum : usize, actual : usize },
+    TooManyCards { maximum : usize, actual : usize },
+    InvalidHandSize { expected : &'static str, actual : usize },
+    TooFewPlayers { minimum : usize, actual : usize },
+    EmptyRange(usize),
+    InvalidRangeWeight(usize),
+    ConflictingRanges,
+    IllegalAction(ActionError),
+    Blackjack(BlackjackError),
+    TienLen(TienLenError)
+}
+
+//rejected moves at a betting table
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ActionError {
+    NotEnoughPlayers,
+    HandInProgress,
+    HandNotInProgress,
+    InvalidSeat(usize),
+    NotYourTurn { expected : usize },
+    CannotCheck,
+    NothingToCall,
+    CannotBet,
+    CannotRaise,
+    BetTooSmall { minimum : u64 },
+    RaiseTooSmall { minimum : u64 },
+    NotEnoughChips,
+    WrongBetSize { expected : u64 },
+    DrawInProgress,
+    NotDrawing,
+    InvalidDiscard,
+    DeckExhausted,
+    ShowdownFailed
+}
+
+impl Display for PokerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PokerError::DeckExhausted => write!(f, "Not enough cards left in the deck"),
+            PokerError::InvalidCard(card) => write!(f, "Card {} is not valid here", card),
+            PokerError::ParseCard(error) => write!(f, "{}", error),
+            PokerError::DuplicateCard(card) => write!(f, "Duplicate card {}", card),
+            PokerError::CardNotInDeck(card) => write!(f, "Card {} is not in the deck", card),
+            PokerError::InvalidDeck => write!(f, "Deck numbers must be distinct and between 1 and 13"),
+            PokerError::TooFewCards { minimum, actual } => write!(f, "Need at least {} cards, got {}", minimum, actual),
+            PokerError::TooManyCards { maximum, actual } => write!(f, "Need at most {} cards, got {}", maximum, actual),
+            PokerError::InvalidHandSize { expected, actual } => write!(f, "Expected {} cards, got {}", expected, actual),
+            PokerError::TooFewPlayers { minimum, actual } => write!(f, "Need at least {} players, got {}", minimum, actual),
+            PokerError::EmptyRange(player) => write!(f, "Range of player {} is empty after removing blocked combos", player),
+            PokerError::InvalidRangeWeight(player) => write!(f, "Range of player {} has invalid weights", player),
+            PokerError::ConflictingRanges => write!(f, "Ranges conflict with each other"),
+            PokerError::IllegalAction(error) => write!(f, "{}", error),
+            PokerError::Blackjack(error) => write!(f, "{}", error),
+            PokerError::TienLen(error) => write!(f, "{}", error)
+        }
+    }
+}
+
+impl Error for PokerError {}
+
+impl Display for ActionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ActionError::NotEnoughPlayers => write!(f, "Need at least two players with chips"),
+            ActionError::HandInProgress => write!(f, "A hand is already in progress"),
+            ActionError::HandNotInProgress => write!(f, "No hand in progress"),
+            ActionError::InvalidSeat(seat) => write!(f, "Seat {} does not exist", seat),
+            ActionError::NotYourTurn { expected } => write!(f, "Seat {} is to act", expected),
+            ActionError::CannotCheck => write!(f, "Cannot check facing a bet"),
+            ActionError::NothingToCall => write!(f, "Nothing to call"),
+            ActionError::CannotBet => write!(f, "Cannot bet when there is already a bet, raise instead"),
+            ActionError::CannotRaise => write!(f, "Raising is not allowed"),
+            ActionError::BetTooSmall { minimum } => write!(f, "Bet must be at least {}", minimum),
+            ActionError::RaiseTooSmall { minimum } => write!(f, "Raise must be to at least {}", minimum),
+            ActionError::NotEnoughChips => write!(f, "Not enough chips"),
+            ActionError::WrongBetSize { expected } => write!(f, "Fixed limit, the bet must be to {}", expected),
+            ActionError::DrawInProgress => write!(f, "Players are drawing, betting is closed"),
+            ActionError::NotDrawing => write!(f, "Cards can only be discarded in a draw round"),
+            ActionError::InvalidDiscard => write!(f, "Discarded cards must be different cards from the hand"),
+            ActionError::DeckExhausted => write!(f, "Deck exhausted"),
+            ActionError::ShowdownFailed => write!(f, "Hands could not be evaluated at showdown")
+        }
+    }
+}
+
+impl Error for ActionError {}
+
+impl From<ParseCardError> for PokerError {
+    fn from(error : ParseCardError) -> Self {
+        match error {
+            ParseCardError::DuplicateCard(card) => PokerError::DuplicateCard(card),
+            other => PokerError::ParseCard(other)
+        }
+    }
+}
+
+impl From<ActionError> for PokerError {
+    fn from(error : ActionError) -> Self {
+        PokerError::IllegalAction(error)
+    }
+}
+
+
+impl From<BlackjackError> for PokerError {
+    fn from(error : BlackjackError) -> Self {
+        PokerError::Blackjack(error)
+    }
+}
+
+impl From<TienLenError> for PokerError {
+    fn from(error : TienLenError) -> Self {
+        PokerError::TienLen(error)
+    }
+}
\ No newline at end of file
@@ -1,3 +1,4 @@
 pub mod entity;
+pub mod error;
 pub mod service;
 pub mod util;
\ No newline at end of file
@@ -0,0 +1,332 @@
+use std::cmp::Ordering;
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+use crate::entity::card::PokerCard;
+use crate::error::ActionError;
+use crate::service::poker_service::Match;
+use crate::service::pot_service::{award_pots_by, build_pots, Pot};
+
+//fixed limit: a bet and three raises per betting round
+const MAX_BETS : u8 = 4;
+
+//Bet and Raise carry the total amount the player has in front of them on this street after the action
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Action {
+    Fold,
+    Check,
+    Call,
+    Bet(u64),
+    Raise(u64),
+    AllIn
+}
+
+//the chips and betting state of a seat; the hold'em, stud and draw seats add their cards and deref to it
+pub struct BettingSeat {
+    stack : u64,
+    street_bet : u64,
+    total_bet : u64,
+    in_hand : bool,
+    folded : bool,
+    all_in : bool,
+    acted : bool,
+    can_raise : bool
+}
+
+//winnings include the player's own chips coming back, so stack before the hand plus winnings minus total bet is the new stack
+pub struct Settlement {
+    winnings : Vec<u64>,
+    winners : Vec<usize>,
+    pots : Vec<Pot>,
+    shown_down : bool
+}
+
+impl BettingSeat {
+    pub fn new(stack : u64) -> Self {
+        BettingSeat {
+            stack,
+            street_bet : 0,
+            total_bet : 0,
+            in_hand : false,
+            folded : false,
+            all_in : false,
+            acted : false,
+            can_raise : true
+        }
+    }
+
+    pub fn get_stack(&self) -> u64 {
+        self.stack
+    }
+
+    pub fn get_street_bet(&self) -> u64 {
+        self.street_bet
+    }
+
+    pub fn get_total_bet(&self) -> u64 {
+        self.total_bet
+    }
+
+    pub fn is_in_hand(&self) -> bool {
+        self.in_hand
+    }
+
+    pub fn is_folded(&self) -> bool {
+        self.folded
+    }
+
+    pub fn is_all_in(&self) -> bool {
+        self.all_in
+    }
+
+    pub(crate) fn is_live(&self) -> bool {
+        self.in_hand && !self.folded
+    }
+
+    pub(crate) fn can_act(&self) -> bool {
+        self.is_live() && !self.all_in
+    }
+
+    //a seat without chips sits the hand out
+    pub(crate) fn sit_in(&mut self) {
+        self.in_hand = self.stack > 0;
+    }
+
+    pub(crate) fn mark_acted(&mut self) {
+        self.acted = true;
+    }
+
+    pub(crate) fn put_chips(&mut self, amount : u64) {
+        let amount = amount.min(self.stack);
+        self.stack -= amount;
+        self.street_bet += amount;
+        self.total_bet += amount;
+        if self.stack == 0 {
+            self.all_in = true;
+        }
+    }
+
+    //an ante is dead money, it goes in the pot without counting toward the street bet
+    pub(crate) fn post_ante(&mut self, amount : u64) {
+        self.put_chips(amount);
+        self.street_bet = 0;
+    }
+}
+
+impl Settlement {
+    pub fn new(winnings : Vec<u64>, winners : Vec<usize>, pots : Vec<Pot>, shown_down : bool) -> Self {
+        Settlement {
+            winnings,
+            winners,
+            pots,
+            shown_down
+        }
+    }
+
+    pub fn get_winnings(&self) -> &Vec<u64> {
+        &self.winnings
+    }
+
+    pub fn get_winners(&self) -> &Vec<usize> {
+        &self.winners
+    }
+
+    pub fn get_pots(&self) -> &Vec<Pot> {
+        &self.pots
+    }
+
+    pub fn is_shown_down(&self) -> bool {
+        self.shown_down
+    }
+}
+
+pub(crate) fn check_turn(hand_in_progress : bool, seat_count : usize, to_act : Option<usize>, seat : usize) -> Result<(), ActionError> {
+    if !hand_in_progress {
+        return Err(ActionError::HandNotInProgress);
+    }
+    if seat >= seat_count {
+        return Err(ActionError::InvalidSeat(seat));
+    }
+    let expected = to_act.ok_or(ActionError::HandNotInProgress)?;
+    if seat != expected {
+        return Err(ActionError::NotYourTurn { expected });
+    }
+    Ok(())
+}
+
+//folds, checks and calls are settled here; a bet, a raise or an all-in above the current bet comes back as
+//the amount to raise to, the game decides the sizes it allows
+pub(crate) fn take_action<S>(seats : &mut [S], seat : usize, action : Action, current_bet : u64) -> Result<Option<u64>, ActionError>
+    where S : DerefMut<Target = BettingSeat> {
+    let street_bet = seats[seat].street_bet;
+    let stack = seats[seat].stack;
+    match action {
+        Action::Fold => {
+            seats[seat].folded = true;
+        }
+        Action::Check => {
+            if street_bet < current_bet {
+                return Err(ActionError::CannotCheck);
+            }
+        }
+        Action::Call => {
+            if street_bet >= current_bet {
+                return Err(ActionError::NothingToCall);
+            }
+            seats[seat].put_chips(current_bet - street_bet);
+        }
+        Action::Bet(amount) => {
+            if current_bet > 0 {
+                return Err(ActionError::CannotBet);
+            }
+            return Ok(Some(amount));
+        }
+        Action::Raise(amount) => {
+            if current_bet == 0 {
+                return Err(ActionError::CannotRaise);
+            }
+            return Ok(Some(amount));
+        }
+        Action::AllIn => {
+            if street_bet + stack > current_bet {
+                return Ok(Some(street_bet + stack));
+            }
+            seats[seat].put_chips(stack);
+        }
+    }
+    Ok(None)
+}
+
+//the chips for a raise must be in the stack and a seat that already acted before a short all-in may only call;
+//tells whether the raise puts the seat all-in
+pub(crate) fn check_raise<S>(seats : &[S], seat : usize, amount : u64, current_bet : u64) -> Result<bool, ActionError>
+    where S : Deref<Target = BettingSeat> {
+    let street_bet = seats[seat].street_bet;
+    let stack = seats[seat].stack;
+    if amount <= street_bet || amount - street_bet > stack {
+        return Err(ActionError::NotEnoughChips);
+    }
+    if current_bet > 0 && !seats[seat].can_raise {
+        return Err(ActionError::CannotRaise);
+    }
+    Ok(amount - street_bet == stack)
+}
+
+//a full raise reopens the betting for everybody, a short all-in raise does not reopen it for players who already acted
+pub(crate) fn put_raise<S>(seats : &mut [S], seat : usize, amount : u64, full_raise : bool)
+    where S : DerefMut<Target = BettingSeat> {
+    let street_bet = seats[seat].street_bet;
+    seats[seat].put_chips(amount - street_bet);
+    for (index, other) in seats.iter_mut().enumerate() {
+        if index == seat || !other.can_act() {
+            continue;
+        }
+        if full_raise {
+            other.can_raise = true;
+        } else if other.acted {
+            other.can_raise = false;
+        }
+        other.acted = false;
+    }
+}
+
+//fixed limit: every bet or raise goes to `expected`, a short all-in raise does not count toward the cap
+pub(crate) fn fixed_limit_raise_to<S>(seats : &mut [S], seat : usize, amount : u64, expected : u64, current_bet : &mut u64, bets : &mut u8) -> Result<(), ActionError>
+    where S : DerefMut<Target = BettingSeat> {
+    let all_in = check_raise(seats, seat, amount, *current_bet)?;
+    if *bets >= MAX_BETS {
+        return Err(ActionError::CannotRaise);
+    }
+    if amount <= *current_bet {
+        return Err(ActionError::RaiseTooSmall { minimum : expected });
+    }
+    if amount != expected && !(all_in && amount < expected) {
+        return Err(ActionError::WrongBetSize { expected });
+    }
+    let full_raise = amount == expected;
+    if full_raise {
+        *bets += 1;
+    }
+    *current_bet = amount;
+    put_raise(seats, seat, amount, full_raise);
+    Ok(())
+}
+
+pub(crate) fn needs_to_act<S>(seats : &[S], index : usize, current_bet : u64) -> bool
+    where S : Deref<Target = BettingSeat> {
+    let seat = &seats[index];
+    if !seat.can_act() {
+        return false;
+    }
+    //nobody left to bet against once every other live player is all-in
+    let others_can_act = seats.iter().enumerate().any(|(other, seat)| other != index && seat.can_act());
+    if !others_can_act && seat.street_bet >= current_bet {
+        return false;
+    }
+    !seat.acted || seat.street_bet < current_bet
+}
+
+pub(crate) fn next_to_act_after<S>(seats : &[S], current : usize, current_bet : u64) -> Option<usize>
+    where S : Deref<Target = BettingSeat> {
+    (1..=seats.len())
+        .map(|offset| (current + offset) % seats.len())
+        .find(|&index| needs_to_act(seats, index, current_bet))
+}
+
+pub(crate) fn start_street<S>(seats : &mut [S])
+    where S : DerefMut<Target = BettingSeat> {
+    for seat in seats.iter_mut() {
+        seat.street_bet = 0;
+        seat.acted = false;
+        seat.can_raise = true;
+    }
+}
+
+//chips nobody could match go straight back to the biggest bettor
+pub(crate) fn return_uncalled_bet<S>(seats : &mut [S])
+    where S : DerefMut<Target = BettingSeat> {
+    let mut bets = seats.iter().map(|seat| seat.total_bet).collect::<Vec<_>>();
+    bets.sort();
+    bets.reverse();
+    if bets.len() < 2 || bets[0] == bets[1] {
+        return;
+    }
+    let uncalled = bets[0] - bets[1];
+    if let Some(seat) = seats.iter_mut().find(|seat| seat.total_bet == bets[0]) {
+        seat.total_bet -= uncalled;
+        seat.street_bet -= uncalled.min(seat.street_bet);
+        seat.stack += uncalled;
+        seat.all_in = seat.all_in && seat.stack == 0;
+    }
+}
+
+//returns the uncalled bet, scores the live seats when more than one is left, then pays every pot out;
+//odd chips start from the first winner left of `button`
+pub(crate) fn settle_by<S, T, F, C>(seats : &mut [S], button : usize, score : F, compare : C) -> Result<Settlement, ActionError>
+    where S : DerefMut<Target = BettingSeat>,
+          F : Fn(&S) -> Result<T, ActionError>,
+          C : Fn(&T, &T) -> Ordering {
+    return_uncalled_bet(seats);
+    let live = (0..seats.len()).filter(|&index| seats[index].is_live()).collect::<Vec<_>>();
+    let shown_down = live.len() > 1;
+    let mut scores : Vec<Option<T>> = (0..seats.len()).map(|_| None).collect();
+    if shown_down {
+        for &index in live.iter() {
+            scores[index] = Some(score(&seats[index])?);
+        }
+    }
+    let contributions = seats.iter().map(|seat| seat.total_bet).collect::<Vec<_>>();
+    let folded = seats.iter().map(|seat| !seat.is_live()).collect::<Vec<_>>();
+    let pots = build_pots(&contributions, &folded);
+    let (winnings, winners) = award_pots_by(&pots, &scores, button, compare);
+    let winners = if shown_down { winners } else { live };
+    for (seat, winning) in seats.iter_mut().zip(winnings.iter()) {
+        seat.stack += winning;
+    }
+    Ok(Settlement::new(winnings, winners, pots, shown_down))
+}
+
+pub(crate) fn draw_cards(poker_match : Option<&mut Match>, number : i16) -> Result<Vec<Rc<PokerCard>>, ActionError> {
+    let poker_match = poker_match.ok_or(ActionError::HandNotInProgress)?;
+    let cards = poker_match.draw(number).map_err(|_| ActionError::DeckExhausted)?;
+    Ok(cards.into_iter().map(Rc::new).collect())
+}
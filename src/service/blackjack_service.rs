@@ -0,0 +1,501 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use crate::entity::card::SolitaireCard;
+use crate::entity::deck::Deck;
+use crate::error::PokerError;
+
+//three splits, so a player plays at most four hands
+const MAX_HANDS_PER_PLAYER : usize = 4;
+const XI_DACH_PLAYER_MINIMUM : i16 = 16;
+const XI_DACH_DEALER_MINIMUM : i16 = 15;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BlackjackVariant {
+    Blackjack { dealer_hits_soft_17 : bool },
+    XiDach
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BlackjackAction {
+    Hit,
+    Stand,
+    Double,
+    Split,
+    TakeInsurance,
+    DeclineInsurance
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BlackjackPhase {
+    Insurance,
+    PlayerTurns,
+    Finished
+}
+
+//XiBan is two aces, XiDach an ace with a ten-value card, NguLinh five cards without busting
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum HandOutcome {
+    Blackjack,
+    XiBan,
+    XiDach,
+    NguLinh,
+    Win,
+    Push,
+    Lose,
+    Bust
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum BlackjackError {
+    RoundInProgress,
+    RoundNotInProgress,
+    InvalidBet,
+    OddBet(u64),
+    InvalidHand(usize),
+    NotYourTurn { expected : usize },
+    IllegalAction(BlackjackAction),
+    NotEnoughPoints { minimum : i16 },
+    ShoeEmpty
+}
+
+pub struct BlackjackHand {
+    player : usize,
+    cards : Vec<SolitaireCard>,
+    bet : u64,
+    insurance : u64,
+    split : bool,
+    doubled : bool,
+    done : bool,
+    outcome : Option<HandOutcome>,
+    payout : i64
+}
+
+pub struct BlackjackTable {
+    variant : BlackjackVariant,
+    shoe : Deck<SolitaireCard>,
+    hands : Vec<BlackjackHand>,
+    dealer_cards : Vec<SolitaireCard>,
+    phase : BlackjackPhase,
+    to_act : Option<usize>
+}
+
+impl BlackjackHand {
+    fn new(player : usize, bet : u64) -> Self {
+        BlackjackHand {
+            player,
+            cards : Vec::new(),
+            bet,
+            insurance : 0,
+            split : false,
+            doubled : false,
+            done : false,
+            outcome : None,
+            payout : 0
+        }
+    }
+
+    pub fn get_player(&self) -> usize {
+        self.player
+    }
+
+    pub fn get_cards(&self) -> &Vec<SolitaireCard> {
+        &self.cards
+    }
+
+    pub fn get_bet(&self) -> u64 {
+        self.bet
+    }
+
+    pub fn get_insurance(&self) -> u64 {
+        self.insurance
+    }
+
+    pub fn is_split(&self) -> bool {
+        self.split
+    }
+
+    pub fn is_doubled(&self) -> bool {
+        self.doubled
+    }
+
+    pub fn get_outcome(&self) -> Option<HandOutcome> {
+        self.outcome
+    }
+
+    //net chips won or lost by the hand, insurance included
+    pub fn get_payout(&self) -> i64 {
+        self.payout
+    }
+
+    //a split hand never counts as a natural
+    fn is_natural(&self) -> bool {
+        !self.split && self.cards.len() == 2 && blackjack_total(&self.cards).0 == 21
+    }
+}
+
+impl BlackjackTable {
+    //the shoe is reshuffled before a round once the cut card is reached
+    pub fn new(variant : BlackjackVariant, shoe : Deck<SolitaireCard>) -> Self {
+        BlackjackTable {
+            variant,
+            shoe,
+            hands : Vec::new(),
+            dealer_cards : Vec::new(),
+            phase : BlackjackPhase::Finished,
+            to_act : None
+        }
+    }
+
+    pub fn get_variant(&self) -> BlackjackVariant {
+        self.variant
+    }
+
+    pub fn get_shoe(&self) -> &Deck<SolitaireCard> {
+        &self.shoe
+    }
+
+    pub fn get_hands(&self) -> &Vec<BlackjackHand> {
+        &self.hands
+    }
+
+    //the second card is the hole card, it stays hidden until the dealer plays
+    pub fn get_dealer_cards(&self) -> &Vec<SolitaireCard> {
+        &self.dealer_cards
+    }
+
+    pub fn get_phase(&self) -> BlackjackPhase {
+        self.phase
+    }
+
+    //index into the hands, not the player
+    pub fn get_to_act(&self) -> Option<usize> {
+        self.to_act
+    }
+
+    //one bet per player, the cards go round twice with the dealer last;
+    //blackjack bets must be even so a natural's 3 to 2 and half bet insurance pay whole chips
+    pub fn start_round(&mut self, bets : &[u64]) -> Result<(), PokerError> {
+        if self.phase != BlackjackPhase::Finished {
+            return Err(BlackjackError::RoundInProgress.into());
+        }
+        if bets.is_empty() || bets.contains(&0) {
+            return Err(BlackjackError::InvalidBet.into());
+        }
+        if let BlackjackVariant::Blackjack { .. } = self.variant {
+            if let Some(&bet) = bets.iter().find(|bet| *bet % 2 == 1) {
+                return Err(BlackjackError::OddBet(bet).into());
+            }
+        }
+        if self.shoe.is_cut_card_reached() {
+            self.shoe.shuffle();
+        }
+        self.hands = bets.iter().enumerate().map(|(player, &bet)| BlackjackHand::new(player, bet)).collect();
+        self.dealer_cards = Vec::new();
+        for _ in 0..2 {
+            for index in 0..self.hands.len() {
+                let card = self.deal()?;
+                self.hands[index].cards.push(card);
+            }
+            let card = self.deal()?;
+            self.dealer_cards.push(card);
+        }
+
+        match self.variant {
+            BlackjackVariant::Blackjack { .. } if self.dealer_cards[0].get_number() == 1 => {
+                self.phase = BlackjackPhase::Insurance;
+                self.to_act = Some(0);
+                Ok(())
+            }
+            _ => Ok(self.after_insurance()?)
+        }
+    }
+
+    pub fn act(&mut self, hand : usize, action : BlackjackAction) -> Result<(), PokerError> {
+        if self.phase == BlackjackPhase::Finished {
+            return Err(BlackjackError::RoundNotInProgress.into());
+        }
+        if hand >= self.hands.len() {
+            return Err(BlackjackError::InvalidHand(hand).into());
+        }
+        let expected = self.to_act.ok_or(BlackjackError::RoundNotInProgress)?;
+        if hand != expected {
+            return Err(BlackjackError::NotYourTurn { expected }.into());
+        }
+
+        if self.phase == BlackjackPhase::Insurance {
+            match action {
+                BlackjackAction::TakeInsurance => self.hands[hand].insurance = self.hands[hand].bet / 2,
+                BlackjackAction::DeclineInsurance => {}
+                _ => return Err(BlackjackError::IllegalAction(action).into())
+            }
+            self.to_act = if hand + 1 < self.hands.len() { Some(hand + 1) } else { None };
+            if self.to_act.is_none() {
+                self.after_insurance()?;
+            }
+            return Ok(());
+        }
+
+        let is_xi_dach = self.variant == BlackjackVariant::XiDach;
+        match action {
+            BlackjackAction::Hit => {
+                let card = self.deal()?;
+                self.hands[hand].cards.push(card);
+                let cards = &self.hands[hand].cards;
+                let (total, _) = self.total(cards);
+                if total >= 21 || (is_xi_dach && cards.len() == 5) {
+                    self.hands[hand].done = true;
+                }
+            }
+            BlackjackAction::Stand => {
+                let (total, _) = self.total(&self.hands[hand].cards);
+                if is_xi_dach && total < XI_DACH_PLAYER_MINIMUM {
+                    return Err(BlackjackError::NotEnoughPoints { minimum : XI_DACH_PLAYER_MINIMUM }.into());
+                }
+                self.hands[hand].done = true;
+            }
+            BlackjackAction::Double => {
+                if is_xi_dach || self.hands[hand].cards.len() != 2 {
+                    return Err(BlackjackError::IllegalAction(action).into());
+                }
+                let card = self.deal()?;
+                let current = &mut self.hands[hand];
+                current.bet *= 2;
+                current.doubled = true;
+                current.cards.push(card);
+                current.done = true;
+            }
+            BlackjackAction::Split => {
+                let player = self.hands[hand].player;
+                let cards = &self.hands[hand].cards;
+                let player_hands = self.hands.iter().filter(|other| other.player == player).count();
+                if is_xi_dach || cards.len() != 2 || card_value(&cards[0]) != card_value(&cards[1]) || player_hands >= MAX_HANDS_PER_PLAYER {
+                    return Err(BlackjackError::IllegalAction(action).into());
+                }
+                let split_aces = cards[0].get_number() == 1;
+                let mut second = BlackjackHand::new(player, self.hands[hand].bet);
+                second.split = true;
+                second.cards.push(self.hands[hand].cards.pop().unwrap());
+                self.hands[hand].split = true;
+                let card = self.deal()?;
+                self.hands[hand].cards.push(card);
+                let card = self.deal()?;
+                second.cards.push(card);
+                //split aces get one card each
+                if split_aces {
+                    self.hands[hand].done = true;
+                    second.done = true;
+                }
+                self.hands.insert(hand + 1, second);
+            }
+            BlackjackAction::TakeInsurance | BlackjackAction::DeclineInsurance => {
+                return Err(BlackjackError::IllegalAction(action).into());
+            }
+        }
+        if self.hands[hand].done {
+            self.advance(hand)?;
+        }
+        Ok(())
+    }
+
+    fn deal(&mut self) -> Result<SolitaireCard, BlackjackError> {
+        self.shoe.draw_one().map_err(|_| BlackjackError::ShoeEmpty)
+    }
+
+    fn total(&self, cards : &[SolitaireCard]) -> (i16, bool) {
+        match self.variant {
+            BlackjackVariant::XiDach => (xi_dach_total(cards), false),
+            _ => blackjack_total(cards)
+        }
+    }
+
+    //the dealer peeks for blackjack, and in Xi Dach both sides show xi ban or xi dach right away
+    fn after_insurance(&mut self) -> Result<(), BlackjackError> {
+        self.phase = BlackjackPhase::PlayerTurns;
+        match self.variant {
+            BlackjackVariant::Blackjack { .. } => {
+                if blackjack_total(&self.dealer_cards).0 == 21 {
+                    return self.settle();
+                }
+                for current in self.hands.iter_mut() {
+                    current.done = current.is_natural();
+                }
+            }
+            BlackjackVariant::XiDach => {
+                if xi_dach_special(&self.dealer_cards).is_some() {
+                    return self.settle();
+                }
+                for current in self.hands.iter_mut() {
+                    current.done = xi_dach_special(&current.cards).is_some();
+                }
+            }
+        }
+        let first = self.hands.iter().position(|current| !current.done);
+        match first {
+            Some(first) => {
+                self.to_act = Some(first);
+                Ok(())
+            }
+            None => self.finish_dealer()
+        }
+    }
+
+    fn advance(&mut self, from : usize) -> Result<(), BlackjackError> {
+        self.to_act = (from..self.hands.len()).find(|&index| !self.hands[index].done);
+        if self.to_act.is_none() {
+            self.finish_dealer()?;
+        }
+        Ok(())
+    }
+
+    //blackjack dealers draw to 17 (and hit soft 17 under H17), Xi Dach dealers draw to 15 or five cards
+    fn finish_dealer(&mut self) -> Result<(), BlackjackError> {
+        let anything_to_play = self.hands.iter().any(|current| match self.variant {
+            BlackjackVariant::XiDach => true,
+            _ => blackjack_total(&current.cards).0 <= 21 && !current.is_natural()
+        });
+        while anything_to_play && self.dealer_hits() {
+            let card = self.deal()?;
+            self.dealer_cards.push(card);
+        }
+        self.settle()
+    }
+
+    fn dealer_hits(&self) -> bool {
+        let (total, soft) = self.total(&self.dealer_cards);
+        match self.variant {
+            BlackjackVariant::Blackjack { dealer_hits_soft_17 } => total < 17 || (total == 17 && soft && dealer_hits_soft_17),
+            BlackjackVariant::XiDach => total < XI_DACH_DEALER_MINIMUM && self.dealer_cards.len() < 5
+        }
+    }
+
+    fn settle(&mut self) -> Result<(), BlackjackError> {
+        let dealer_cards = self.dealer_cards.clone();
+        for current in self.hands.iter_mut() {
+            let (outcome, payout) = match self.variant {
+                BlackjackVariant::Blackjack { .. } => blackjack_result(current, &dealer_cards),
+                BlackjackVariant::XiDach => xi_dach_result(current, &dealer_cards)
+            };
+            current.outcome = Some(outcome);
+            current.payout = payout;
+            current.done = true;
+        }
+        self.phase = BlackjackPhase::Finished;
+        self.to_act = None;
+        Ok(())
+    }
+}
+
+//ace as 11 when it does not bust the hand, soft tells whether an ace is still counted as 11
+pub fn blackjack_total(cards : &[SolitaireCard]) -> (i16, bool) {
+    let hard = cards.iter().map(card_value).sum::<i16>();
+    if cards.iter().any(|card| card.get_number() == 1) && hard + 10 <= 21 {
+        (hard + 10, true)
+    } else {
+        (hard, false)
+    }
+}
+
+//an ace is worth 1, 10 or 11 with two cards, 1 or 10 with three cards and only 1 from four cards on
+pub fn xi_dach_total(cards : &[SolitaireCard]) -> i16 {
+    let hard = cards.iter().map(card_value).sum::<i16>();
+    let extras : &[i16] = match cards.len() {
+        0..=2 => &[0, 9, 10],
+        3 => &[0, 9],
+        _ => &[0]
+    };
+    //every ace adds one of the extras on top of the 1 already counted
+    let mut totals = vec![hard];
+    for _ in cards.iter().filter(|card| card.get_number() == 1) {
+        totals = totals.iter().flat_map(|total| extras.iter().map(move |extra| total + extra)).collect();
+    }
+    totals.into_iter().filter(|&total| total <= 21).max().unwrap_or(hard)
+}
+
+pub fn xi_dach_special(cards : &[SolitaireCard]) -> Option<HandOutcome> {
+    if cards.len() == 2 && cards.iter().all(|card| card.get_number() == 1) {
+        Some(HandOutcome::XiBan)
+    } else if cards.len() == 2 && blackjack_total(cards).0 == 21 {
+        Some(HandOutcome::XiDach)
+    } else if cards.len() == 5 && xi_dach_total(cards) <= 21 {
+        Some(HandOutcome::NguLinh)
+    } else {
+        None
+    }
+}
+
+fn card_value(card : &SolitaireCard) -> i16 {
+    card.get_number().min(10)
+}
+
+//naturals pay 3 to 2, insurance pays 2 to 1; a busted hand loses even when the dealer busts too
+fn blackjack_result(hand : &BlackjackHand, dealer_cards : &[SolitaireCard]) -> (HandOutcome, i64) {
+    let bet = hand.bet as i64;
+    let dealer_natural = dealer_cards.len() == 2 && blackjack_total(dealer_cards).0 == 21;
+    let insurance = if dealer_natural { hand.insurance as i64 * 2 } else { -(hand.insurance as i64) };
+    let (player_total, _) = blackjack_total(&hand.cards);
+    let (dealer_total, _) = blackjack_total(dealer_cards);
+    let (outcome, payout) = if hand.is_natural() {
+        if dealer_natural { (HandOutcome::Push, 0) } else { (HandOutcome::Blackjack, bet * 3 / 2) }
+    } else if dealer_natural {
+        (HandOutcome::Lose, -bet)
+    } else if player_total > 21 {
+        (HandOutcome::Bust, -bet)
+    } else if dealer_total > 21 {
+        (HandOutcome::Win, bet)
+    } else {
+        match player_total.cmp(&dealer_total) {
+            Ordering::Greater => (HandOutcome::Win, bet),
+            Ordering::Equal => (HandOutcome::Push, 0),
+            Ordering::Less => (HandOutcome::Lose, -bet)
+        }
+    };
+    (outcome, payout + insurance)
+}
+
+//xi ban pays 3 times the bet, xi dach and ngu linh twice, whichever side holds them;
+//two busted hands push, and between two ngu linh the smaller total wins
+fn xi_dach_result(hand : &BlackjackHand, dealer_cards : &[SolitaireCard]) -> (HandOutcome, i64) {
+    let bet = hand.bet as i64;
+    let rank = |cards : &[SolitaireCard]| match xi_dach_special(cards) {
+        Some(HandOutcome::XiBan) => (4, 0),
+        Some(HandOutcome::XiDach) => (3, 0),
+        Some(_) => (2, -xi_dach_total(cards)),
+        None if xi_dach_total(cards) > 21 => (0, 0),
+        None => (1, xi_dach_total(cards))
+    };
+    let multiplier = |cards : &[SolitaireCard]| match xi_dach_special(cards) {
+        Some(HandOutcome::XiBan) => 3,
+        Some(_) => 2,
+        None => 1
+    };
+    let player = rank(&hand.cards);
+    let dealer = rank(dealer_cards);
+    if player.0 == 0 && dealer.0 == 0 {
+        return (HandOutcome::Push, 0);
+    }
+    match player.cmp(&dealer) {
+        Ordering::Greater => (xi_dach_special(&hand.cards).unwrap_or(HandOutcome::Win), bet * multiplier(&hand.cards)),
+        Ordering::Equal => (HandOutcome::Push, 0),
+        Ordering::Less if player.0 == 0 => (HandOutcome::Bust, -bet * multiplier(dealer_cards)),
+        Ordering::Less => (HandOutcome::Lose, -bet * multiplier(dealer_cards))
+    }
+}
+
+impl Display for BlackjackError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlackjackError::RoundInProgress => write!(f, "A round is already in progress"),
+            BlackjackError::RoundNotInProgress => write!(f, "No round in progress"),
+            BlackjackError::InvalidBet => write!(f, "Every player needs a bet above zero"),
+            BlackjackError::OddBet(bet) => write!(f, "Bet {} is odd, blackjack bets must be even", bet),
+            BlackjackError::InvalidHand(hand) => write!(f, "Hand {} does not exist", hand),
+            BlackjackError::NotYourTurn { expected } => write!(f, "Hand {} is to act", expected),
+            BlackjackError::IllegalAction(action) => write!(f, "{:?} is not allowed now", action),
+            BlackjackError::NotEnoughPoints { minimum } => write!(f, "Need at least {} points to stand", minimum),
+            BlackjackError::ShoeEmpty => write!(f, "Shoe is empty")
+        }
+    }
+}
+
+impl Error for BlackjackError {}
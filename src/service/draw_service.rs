@@ -0,0 +1,321 @@
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+use crate::entity::card::PokerCard;
+use crate::error::{ActionError, PokerError};
+use crate::service::betting_service::{check_turn, draw_cards, fixed_limit_raise_to, next_to_act_after, settle_by, start_street, take_action, Action, BettingSeat, Settlement};
+use crate::service::lowball_service::{compare_lowball_score, deuce_to_seven_score};
+use crate::service::poker_service::{compare_score, score_by_one_deck, Match};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DrawGame {
+    FiveCardDraw,
+    DeuceToSevenSingleDraw,
+    DeuceToSevenTripleDraw
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DrawStreet {
+    Betting,
+    Drawing,
+    Showdown
+}
+
+pub struct DrawSeat {
+    betting : BettingSeat,
+    hand : Vec<Rc<PokerCard>>,
+    drawn : bool
+}
+
+//the small bet is the big blind, it doubles for the second half of the betting rounds
+pub struct DrawTable {
+    game : DrawGame,
+    seats : Vec<DrawSeat>,
+    small_blind : u64,
+    big_blind : u64,
+    button : Option<usize>,
+    poker_match : Option<Match>,
+    street : DrawStreet,
+    draws_done : u8,
+    current_bet : u64,
+    bets : u8,
+    to_act : Option<usize>,
+    settlement : Option<Settlement>
+}
+
+impl DrawGame {
+    pub fn get_draw_rounds(&self) -> u8 {
+        match self {
+            DrawGame::FiveCardDraw | DrawGame::DeuceToSevenSingleDraw => 1,
+            DrawGame::DeuceToSevenTripleDraw => 3
+        }
+    }
+
+    pub fn is_low(&self) -> bool {
+        !matches!(self, DrawGame::FiveCardDraw)
+    }
+}
+
+impl DrawSeat {
+    fn new(stack : u64) -> Self {
+        DrawSeat {
+            betting : BettingSeat::new(stack),
+            hand : Vec::new(),
+            drawn : false
+        }
+    }
+
+    pub fn get_hand(&self) -> &Vec<Rc<PokerCard>> {
+        &self.hand
+    }
+}
+
+impl Deref for DrawSeat {
+    type Target = BettingSeat;
+
+    fn deref(&self) -> &BettingSeat {
+        &self.betting
+    }
+}
+
+impl DerefMut for DrawSeat {
+    fn deref_mut(&mut self) -> &mut BettingSeat {
+        &mut self.betting
+    }
+}
+
+impl DrawTable {
+    pub fn new(game : DrawGame, stacks : Vec<u64>, small_blind : u64, big_blind : u64) -> Self {
+        DrawTable {
+            game,
+            seats : stacks.into_iter().map(DrawSeat::new).collect(),
+            small_blind,
+            big_blind,
+            button : None,
+            poker_match : None,
+            street : DrawStreet::Showdown,
+            draws_done : 0,
+            current_bet : 0,
+            bets : 0,
+            to_act : None,
+            settlement : None
+        }
+    }
+
+    pub fn get_game(&self) -> DrawGame {
+        self.game
+    }
+
+    pub fn get_seats(&self) -> &Vec<DrawSeat> {
+        &self.seats
+    }
+
+    pub fn get_button(&self) -> Option<usize> {
+        self.button
+    }
+
+    pub fn get_street(&self) -> DrawStreet {
+        self.street
+    }
+
+    pub fn get_draws_done(&self) -> u8 {
+        self.draws_done
+    }
+
+    pub fn get_to_act(&self) -> Option<usize> {
+        self.to_act
+    }
+
+    pub fn get_current_bet(&self) -> u64 {
+        self.current_bet
+    }
+
+    pub fn get_bet_size(&self) -> u64 {
+        if self.draws_done * 2 < self.game.get_draw_rounds() + 1 {
+            self.big_blind
+        } else {
+            self.big_blind * 2
+        }
+    }
+
+    pub fn get_raise_to(&self) -> u64 {
+        self.current_bet + self.get_bet_size()
+    }
+
+    pub fn get_pot(&self) -> u64 {
+        self.seats.iter().map(|seat| seat.get_total_bet()).sum()
+    }
+
+    pub fn get_call_amount(&self, seat : usize) -> u64 {
+        self.seats.get(seat).map_or(0, |seat| self.current_bet.saturating_sub(seat.get_street_bet()).min(seat.get_stack()))
+    }
+
+    pub fn get_settlement(&self) -> Option<&Settlement> {
+        self.settlement.as_ref()
+    }
+
+    pub fn is_hand_in_progress(&self) -> bool {
+        self.poker_match.is_some()
+    }
+
+    pub fn start_hand(&mut self, poker_match : Match) -> Result<(), PokerError> {
+        if self.is_hand_in_progress() {
+            return Err(ActionError::HandInProgress.into());
+        }
+        let players = self.seats.iter().filter(|seat| seat.get_stack() > 0).count();
+        if players < 2 {
+            return Err(ActionError::NotEnoughPlayers.into());
+        }
+        for seat in self.seats.iter_mut() {
+            *seat = DrawSeat::new(seat.get_stack());
+            seat.sit_in();
+        }
+        let button = match self.button {
+            Some(button) => self.next_seat(button),
+            None => self.next_seat(self.seats.len() - 1)
+        };
+        self.button = Some(button);
+        self.poker_match = Some(poker_match);
+        self.settlement = None;
+        self.street = DrawStreet::Betting;
+        self.draws_done = 0;
+
+        //heads-up the button posts the small blind, the big blind counts as the first bet
+        let small_blind_seat = if players == 2 { button } else { self.next_seat(button) };
+        let big_blind_seat = self.next_seat(small_blind_seat);
+        self.seats[small_blind_seat].put_chips(self.small_blind);
+        self.seats[big_blind_seat].put_chips(self.big_blind);
+        self.current_bet = self.big_blind;
+        self.bets = 1;
+
+        for index in 0..self.seats.len() {
+            if self.seats[index].is_in_hand() {
+                self.seats[index].hand = self.draw(5)?;
+            }
+        }
+        self.to_act = next_to_act_after(&self.seats, big_blind_seat, self.current_bet);
+        if self.to_act.is_none() {
+            self.finish_round()?;
+        }
+        Ok(())
+    }
+
+    //an all-in is only for a stack that cannot cover a full bet, the limit fixes every other amount
+    pub fn act(&mut self, seat : usize, action : Action) -> Result<(), PokerError> {
+        check_turn(self.is_hand_in_progress(), self.seats.len(), self.to_act, seat)?;
+        if self.street != DrawStreet::Betting {
+            return Err(ActionError::DrawInProgress.into());
+        }
+        if let Some(amount) = take_action(&mut self.seats, seat, action, self.current_bet)? {
+            let expected = self.get_raise_to();
+            fixed_limit_raise_to(&mut self.seats, seat, amount, expected, &mut self.current_bet, &mut self.bets)?;
+        }
+        self.seats[seat].mark_acted();
+
+        if self.seats.iter().filter(|seat| seat.is_live()).count() == 1 {
+            self.settle()?;
+        } else {
+            self.to_act = next_to_act_after(&self.seats, seat, self.current_bet);
+            if self.to_act.is_none() {
+                self.finish_round()?;
+            }
+        }
+        Ok(())
+    }
+
+    //an empty discard stands pat; the replacements come before the discards reach the muck,
+    //so a player never gets their own cards back
+    pub fn discard<C: Deref<Target = PokerCard>>(&mut self, seat : usize, cards : &[C]) -> Result<(), PokerError> {
+        check_turn(self.is_hand_in_progress(), self.seats.len(), self.to_act, seat)?;
+        if self.street != DrawStreet::Drawing {
+            return Err(ActionError::NotDrawing.into());
+        }
+        let mut positions : Vec<usize> = Vec::new();
+        for card in cards.iter() {
+            match self.seats[seat].hand.iter().position(|other| other.is_same_card(card)) {
+                Some(position) if !positions.contains(&position) => positions.push(position),
+                _ => return Err(ActionError::InvalidDiscard.into())
+            }
+        }
+        let mut replacements = self.draw(positions.len() as i16)?;
+        positions.sort();
+        let mut discarded : Vec<Rc<PokerCard>> = Vec::new();
+        for &position in positions.iter().rev() {
+            discarded.push(self.seats[seat].hand.remove(position));
+        }
+        self.seats[seat].hand.append(&mut replacements);
+        if let Some(poker_match) = self.poker_match.as_mut() {
+            poker_match.discard(&discarded);
+        }
+        self.seats[seat].drawn = true;
+
+        self.to_act = self.next_to_draw_after(seat);
+        if self.to_act.is_none() {
+            self.finish_draw()?;
+        }
+        Ok(())
+    }
+
+    //all-in players still draw
+    fn next_to_draw_after(&self, current : usize) -> Option<usize> {
+        (1..=self.seats.len())
+            .map(|offset| (current + offset) % self.seats.len())
+            .find(|&index| self.seats[index].is_live() && !self.seats[index].drawn)
+    }
+
+    fn next_seat(&self, current : usize) -> usize {
+        (1..=self.seats.len())
+            .map(|offset| (current + offset) % self.seats.len())
+            .find(|&index| self.seats[index].is_in_hand())
+            .unwrap_or(current)
+    }
+
+    //a finished betting round opens the next draw, or the showdown after the last one
+    fn finish_round(&mut self) -> Result<(), ActionError> {
+        if self.draws_done >= self.game.get_draw_rounds() {
+            return self.settle();
+        }
+        self.street = DrawStreet::Drawing;
+        for seat in self.seats.iter_mut() {
+            seat.drawn = false;
+        }
+        self.to_act = self.next_to_draw_after(self.button.unwrap_or(0));
+        Ok(())
+    }
+
+    fn finish_draw(&mut self) -> Result<(), ActionError> {
+        self.draws_done += 1;
+        self.street = DrawStreet::Betting;
+        start_street(&mut self.seats);
+        self.current_bet = 0;
+        self.bets = 0;
+        self.to_act = next_to_act_after(&self.seats, self.button.unwrap_or(0), self.current_bet);
+        if self.to_act.is_none() {
+            self.finish_round()?;
+        }
+        Ok(())
+    }
+
+    fn settle(&mut self) -> Result<(), ActionError> {
+        let button = self.button.unwrap_or(0);
+        let settlement = if self.game.is_low() {
+            settle_by(&mut self.seats, button, |seat| {
+                let (score, _) = deuce_to_seven_score(&seat.hand).map_err(|_| ActionError::ShowdownFailed)?;
+                Ok(score)
+            }, compare_lowball_score)?
+        } else {
+            settle_by(&mut self.seats, button, |seat| {
+                let (score, _, _) = score_by_one_deck(&seat.hand).map_err(|_| ActionError::ShowdownFailed)?;
+                Ok(score)
+            }, compare_score)?
+        };
+        self.street = DrawStreet::Showdown;
+        self.to_act = None;
+        self.poker_match = None;
+        self.settlement = Some(settlement);
+        Ok(())
+    }
+
+    fn draw(&mut self, number : i16) -> Result<Vec<Rc<PokerCard>>, ActionError> {
+        draw_cards(self.poker_match.as_mut(), number)
+    }
+}
@@ -0,0 +1,352 @@
+use std::ops::Range;
+use std::rc::Rc;
+use rand::{Rng, SeedableRng};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::SliceRandom;
+use rand_chacha::ChaCha8Rng;
+use crate::entity::card::{Card, PokerCard};
+use crate::entity::card_set::CardSet;
+use crate::entity::range::HandRange;
+use crate::error::PokerError;
+use crate::service::fast_poker_service::fast_score_by_cards;
+use crate::service::poker_service::{showdown, Match};
+use crate::service::simulation_service::Simulation;
+
+pub enum EquityMode {
+    Exhaustive,
+    MonteCarlo { iterations : u64, seed : u64 }
+}
+
+//tie counts boards where the player shares the pot, equity also adds the player's share of those pots
+#[derive(Debug, Clone, Default)]
+pub struct PlayerEquity {
+    wins : u64,
+    ties : u64,
+    losses : u64,
+    share : f64
+}
+
+#[derive(Debug, Clone)]
+pub struct EquityResult {
+    players : Vec<PlayerEquity>,
+    boards : u64
+}
+
+impl PlayerEquity {
+    pub fn get_wins(&self) -> u64 {
+        self.wins
+    }
+
+    pub fn get_ties(&self) -> u64 {
+        self.ties
+    }
+
+    pub fn get_losses(&self) -> u64 {
+        self.losses
+    }
+
+    fn percent(&self, count : f64) -> f64 {
+        let total = self.wins + self.ties + self.losses;
+        if total == 0 { 0.0 } else { count * 100.0 / total as f64 }
+    }
+
+    pub fn get_win_percent(&self) -> f64 {
+        self.percent(self.wins as f64)
+    }
+
+    pub fn get_tie_percent(&self) -> f64 {
+        self.percent(self.ties as f64)
+    }
+
+    pub fn get_lose_percent(&self) -> f64 {
+        self.percent(self.losses as f64)
+    }
+
+    pub fn get_equity_percent(&self) -> f64 {
+        self.percent(self.share)
+    }
+}
+
+impl EquityResult {
+    fn new(players : usize) -> Self {
+        EquityResult {
+            players : vec![PlayerEquity::default(); players],
+            boards : 0
+        }
+    }
+
+    fn merge(&mut self, other : &EquityResult) {
+        for (player, other_player) in self.players.iter_mut().zip(other.players.iter()) {
+            player.wins += other_player.wins;
+            player.ties += other_player.ties;
+            player.losses += other_player.losses;
+            player.share += other_player.share;
+        }
+        self.boards += other.boards;
+    }
+
+    pub fn get_players(&self) -> &Vec<PlayerEquity> {
+        &self.players
+    }
+
+    pub fn get_boards(&self) -> u64 {
+        self.boards
+    }
+}
+
+pub fn calculate_equity(player_decks : &[Vec<Rc<PokerCard>>],
+                        general_deck : &[Rc<PokerCard>],
+                        dead_cards : &[Rc<PokerCard>],
+                        mode : &EquityMode) -> Result<EquityResult, PokerError> {
+    if player_decks.len() < 2 {
+        return Err(PokerError::TooFewPlayers { minimum : 2, actual : player_decks.len() });
+    }
+    if let Some(deck) = player_decks.iter().find(|deck| deck.len() != 2) {
+        return Err(PokerError::InvalidHandSize { expected : "two hole", actual : deck.len() });
+    }
+    if general_deck.len() > 5 {
+        return Err(PokerError::TooManyCards { maximum : 5, actual : general_deck.len() });
+    }
+    let mut result = EquityResult::new(player_decks.len());
+    let known_match = new_match_without_known(0, player_decks, general_deck, dead_cards)?;
+    let mut remaining = known_match.get_deck().iter().map(|card| Rc::new(card.get_copy())).collect::<Vec<_>>();
+    let missing = 5 - general_deck.len();
+    if remaining.len() < missing {
+        return Err(PokerError::DeckExhausted);
+    }
+
+    let mut board = general_deck.to_vec();
+    match mode {
+        EquityMode::Exhaustive => {
+            let boards = combination_count(remaining.len(), missing);
+            for_each_board(&remaining, missing, 0..boards, &mut board, &mut |board| {
+                add_board(&mut result, player_decks, board)
+            })?;
+        }
+        EquityMode::MonteCarlo { iterations, seed } => {
+            let mut rng = ChaCha8Rng::seed_from_u64(*seed);
+            for _ in 0..*iterations {
+                board.truncate(general_deck.len());
+                board.extend(remaining.partial_shuffle(&mut rng, missing).0.iter().map(Rc::clone));
+                add_board(&mut result, player_decks, &board)?;
+            }
+        }
+    }
+    Ok(result)
+}
+
+//same inputs as calculate_equity on Copy cards, split over the simulation's workers; exhaustive results do not
+//depend on the worker count, monte carlo results replay for the same seed and worker count
+pub fn calculate_equity_parallel(player_decks : &[Vec<Card>],
+                                 general_deck : &[Card],
+                                 dead_cards : &[Card],
+                                 mode : &EquityMode,
+                                 workers : usize) -> Result<EquityResult, PokerError> {
+    if player_decks.len() < 2 {
+        return Err(PokerError::TooFewPlayers { minimum : 2, actual : player_decks.len() });
+    }
+    if let Some(deck) = player_decks.iter().find(|deck| deck.len() != 2) {
+        return Err(PokerError::InvalidHandSize { expected : "two hole", actual : deck.len() });
+    }
+    if general_deck.len() > 5 {
+        return Err(PokerError::TooManyCards { maximum : 5, actual : general_deck.len() });
+    }
+    let mut known = CardSet::new();
+    for card in player_decks.iter().flatten().chain(general_deck.iter()).chain(dead_cards.iter()) {
+        if !known.insert(*card) {
+            return Err(PokerError::DuplicateCard(card.to_string()));
+        }
+    }
+    let remaining = CardSet::new_full().difference(&known).iter().collect::<Vec<_>>();
+    let missing = 5 - general_deck.len();
+    if remaining.len() < missing {
+        return Err(PokerError::DeckExhausted);
+    }
+
+    let partials = match mode {
+        EquityMode::Exhaustive => {
+            let simulation = Simulation::new(workers, 0);
+            let boards = combination_count(remaining.len(), missing);
+            simulation.run(|worker, _| -> Result<EquityResult, PokerError> {
+                let mut partial = EquityResult::new(player_decks.len());
+                let mut board = general_deck.to_vec();
+                for_each_board(&remaining, missing, simulation.get_range(boards, worker), &mut board, &mut |board| {
+                    add_card_board(&mut partial, player_decks, board)
+                })?;
+                Ok(partial)
+            })
+        }
+        EquityMode::MonteCarlo { iterations, seed } => {
+            let simulation = Simulation::new(workers, *seed);
+            simulation.run(|worker, mut rng| -> Result<EquityResult, PokerError> {
+                let mut partial = EquityResult::new(player_decks.len());
+                let mut stub = remaining.clone();
+                let mut board = general_deck.to_vec();
+                for _ in 0..simulation.get_share(*iterations, worker) {
+                    board.truncate(general_deck.len());
+                    board.extend_from_slice(stub.partial_shuffle(&mut rng, missing).0);
+                    add_card_board(&mut partial, player_decks, &board)?;
+                }
+                Ok(partial)
+            })
+        }
+    };
+    let mut result = EquityResult::new(player_decks.len());
+    for partial in partials {
+        result.merge(&partial?);
+    }
+    Ok(result)
+}
+
+//hand-vs-range uses HandRange::from_cards for the known hand; combos are drawn by weight and
+//a draw where two players hold the same card is thrown away so the joint distribution stays fair
+pub fn calculate_range_equity(ranges : &[HandRange],
+                              general_deck : &[Rc<PokerCard>],
+                              dead_cards : &[Rc<PokerCard>],
+                              iterations : u64,
+                              seed : u64) -> Result<EquityResult, PokerError> {
+    if ranges.len() < 2 {
+        return Err(PokerError::TooFewPlayers { minimum : 2, actual : ranges.len() });
+    }
+    if general_deck.len() > 5 {
+        return Err(PokerError::TooManyCards { maximum : 5, actual : general_deck.len() });
+    }
+    let known = general_deck.iter().chain(dead_cards.iter()).map(Rc::clone).collect::<Vec<_>>();
+    let mut range_combos : Vec<Vec<Vec<Rc<PokerCard>>>> = Vec::new();
+    let mut range_weights : Vec<WeightedIndex<f64>> = Vec::new();
+    for (index, range) in ranges.iter().enumerate() {
+        let combos = range.get_combos().iter().filter(|combo| !combo.is_blocked_by(&known)).collect::<Vec<_>>();
+        if combos.is_empty() {
+            return Err(PokerError::EmptyRange(index));
+        }
+        range_weights.push(WeightedIndex::new(combos.iter().map(|combo| combo.get_weight())).map_err(|_| PokerError::InvalidRangeWeight(index))?);
+        range_combos.push(combos.iter()
+            .map(|combo| combo.get_cards().iter().map(|card| Rc::new(card.get_copy())).collect())
+            .collect());
+    }
+
+    let mut result = EquityResult::new(ranges.len());
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut attempts = 0_u64;
+    while result.boards < iterations {
+        attempts += 1;
+        if attempts > iterations.saturating_mul(100).max(1000) {
+            return Err(PokerError::ConflictingRanges);
+        }
+        let player_decks = range_combos.iter().zip(range_weights.iter())
+            .map(|(combos, weights)| combos[weights.sample(&mut rng)].clone())
+            .collect::<Vec<_>>();
+        let held = player_decks.iter().flatten().map(Rc::clone).collect::<Vec<_>>();
+        if CardSet::new_by_cards(&held).is_err() {
+            continue;
+        }
+        let mut poker_match = new_match_without_known(rng.gen(), &player_decks, general_deck, dead_cards)?;
+        let mut board = general_deck.to_vec();
+        board.extend(poker_match.draw(5 - general_deck.len() as i16)?.into_iter().map(Rc::new));
+        add_board(&mut result, &player_decks, &board)?;
+    }
+    Ok(result)
+}
+
+fn new_match_without_known(seed : u64,
+                           player_decks : &[Vec<Rc<PokerCard>>],
+                           general_deck : &[Rc<PokerCard>],
+                           dead_cards : &[Rc<PokerCard>]) -> Result<Match, PokerError> {
+    let mut poker_match = Match::new_with_seed(seed);
+    for deck in player_decks.iter() {
+        poker_match.remove_cards(deck)?;
+    }
+    poker_match.remove_cards(general_deck)?;
+    poker_match.remove_cards(dead_cards)?;
+    Ok(poker_match)
+}
+
+fn add_board(result : &mut EquityResult, player_decks : &[Vec<Rc<PokerCard>>], board : &[Rc<PokerCard>]) -> Result<(), PokerError> {
+    let winners = showdown(player_decks, board)?.get_winners().clone();
+    add_winners(result, &winners);
+    Ok(())
+}
+
+fn add_card_board(result : &mut EquityResult, player_decks : &[Vec<Card>], board : &[Card]) -> Result<(), PokerError> {
+    let mut strengths = Vec::with_capacity(player_decks.len());
+    for deck in player_decks.iter() {
+        let cards = deck.iter().chain(board.iter()).copied().collect::<Vec<_>>();
+        strengths.push(fast_score_by_cards(&cards)?);
+    }
+    let best = strengths.iter().max().copied().unwrap_or_default();
+    let winners = (0..strengths.len()).filter(|&index| strengths[index] == best).collect::<Vec<_>>();
+    add_winners(result, &winners);
+    Ok(())
+}
+
+fn add_winners(result : &mut EquityResult, winners : &[usize]) {
+    for (index, player) in result.players.iter_mut().enumerate() {
+        if !winners.contains(&index) {
+            player.losses += 1;
+        } else if winners.len() == 1 {
+            player.wins += 1;
+            player.share += 1.0;
+        } else {
+            player.ties += 1;
+            player.share += 1.0 / winners.len() as f64;
+        }
+    }
+    result.boards += 1;
+}
+
+//the combinations of `missing` cards from `remaining` numbered `boards` in lexicographic order, each one
+//appended to the known board; workers start from their own number instead of walking the ones before it
+fn for_each_board<T : Clone>(remaining : &[T],
+                            missing : usize,
+                            boards : Range<u64>,
+                            board : &mut Vec<T>,
+                            action : &mut impl FnMut(&[T]) -> Result<(), PokerError>) -> Result<(), PokerError> {
+    if remaining.len() < missing {
+        return Err(PokerError::DeckExhausted);
+    }
+    if boards.is_empty() {
+        return Ok(());
+    }
+    let known = board.len();
+    let mut indexes = nth_combination(remaining.len(), missing, boards.start);
+    for _ in boards {
+        board.truncate(known);
+        board.extend(indexes.iter().map(|&index| remaining[index].clone()));
+        action(board)?;
+        //the rightmost index that can still move up moves, the ones after it follow right behind
+        if let Some(position) = (0..missing).rev().find(|&position| indexes[position] < remaining.len() - missing + position) {
+            indexes[position] += 1;
+            for next in position + 1..missing {
+                indexes[next] = indexes[next - 1] + 1;
+            }
+        }
+    }
+    board.truncate(known);
+    Ok(())
+}
+
+//indexes of the combination numbered `rank` among all `size`-card combinations of `items` cards
+fn nth_combination(items : usize, size : usize, mut rank : u64) -> Vec<usize> {
+    let mut indexes = Vec::with_capacity(size);
+    let mut candidate = 0;
+    for position in 0..size {
+        loop {
+            let with_candidate = combination_count(items - candidate - 1, size - position - 1);
+            if rank < with_candidate {
+                break;
+            }
+            rank -= with_candidate;
+            candidate += 1;
+        }
+        indexes.push(candidate);
+        candidate += 1;
+    }
+    indexes
+}
+
+fn combination_count(items : usize, size : usize) -> u64 {
+    if size > items {
+        return 0;
+    }
+    (0..size).fold(1_u64, |count, i| count * (items - i) as u64 / (i + 1) as u64)
+}
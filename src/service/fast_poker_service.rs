@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::OnceLock;
+use crate::entity::card::{Card, PokerCard};
+use crate::entity::card_set::CardSet;
+use crate::entity::hand::HandCategory;
+use crate::entity::hand::HandCategory::*;
+use crate::error::PokerError;
+
+//higher is stronger, 1 is the worst high card and 7462 the royal flush
+pub type HandStrength = u16;
+
+const PRIMES : [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+const WHEEL_MASK : u16 = 0b1_0000_0000_1111;
+
+//flush and unique are indexed by the 13 bit mask of the ranks (2 is bit 0, ace is bit 12),
+//hands with a repeated rank are found by the product of one prime per card rank
+struct LookupTable {
+    flush : Vec<HandStrength>,
+    unique : Vec<HandStrength>,
+    paired : HashMap<u32, HandStrength>,
+    categories : Vec<HandCategory>
+}
+
+static TABLE : OnceLock<LookupTable> = OnceLock::new();
+
+pub fn fast_score<C: Deref<Target = PokerCard>>(cards : &[C]) -> Result<HandStrength, PokerError> {
+    if cards.len() < 5 || cards.len() > 7 {
+        return Err(PokerError::InvalidHandSize { expected : "five to seven", actual : cards.len() });
+    }
+    //jokers, numbers outside 1..=13 and repeated cards are rejected here, so every hand below has a table entry
+    let encoded = CardSet::new_by_cards(cards)?.iter().map(|card| card.get_index()).collect::<Vec<_>>();
+    Ok(score_encoded(&encoded))
+}
+
+//Card is Copy and Send, so worker threads can score without touching Rc
+pub fn fast_score_by_cards(cards : &[Card]) -> Result<HandStrength, PokerError> {
+    if cards.len() < 5 || cards.len() > 7 {
+        return Err(PokerError::InvalidHandSize { expected : "five to seven", actual : cards.len() });
+    }
+    let mut set = CardSet::new();
+    for card in cards.iter() {
+        if !set.insert(*card) {
+            return Err(PokerError::DuplicateCard(card.to_string()));
+        }
+    }
+    let encoded = set.iter().map(|card| card.get_index()).collect::<Vec<_>>();
+    Ok(score_encoded(&encoded))
+}
+
+pub fn fast_score_by_two_deck<C: Deref<Target = PokerCard>>(player_deck : &[C], general_deck : &[C]) -> Result<HandStrength, PokerError> {
+    let deck = player_deck.iter().chain(general_deck.iter()).map(|card| card.deref()).collect::<Vec<_>>();
+    fast_score(&deck)
+}
+
+//strength as returned by fast_score, 1..=7462
+pub fn strength_category(strength : HandStrength) -> HandCategory {
+    get_table().categories[strength as usize - 1]
+}
+
+//cards are Card indexes: rank 0..12 (2..ace) in the high bits, suit in the low two bits; the callers
+//reject repeated cards, so every five of them has a table entry
+fn score_encoded(encoded : &[u8]) -> HandStrength {
+    let table = get_table();
+    let mut best = 0;
+    let mut chosen = [0_u8; 5];
+    for_each_five(encoded, 0, 0, &mut chosen, &mut |five| {
+        best = best.max(score_five(table, five));
+    });
+    best
+}
+
+fn for_each_five(cards : &[u8], start : usize, depth : usize, chosen : &mut [u8; 5], action : &mut impl FnMut(&[u8; 5])) {
+    if depth == 5 {
+        action(chosen);
+        return;
+    }
+    for i in start..=(cards.len() + depth - 5) {
+        chosen[depth] = cards[i];
+        for_each_five(cards, i + 1, depth + 1, chosen, action);
+    }
+}
+
+fn score_five(table : &LookupTable, cards : &[u8; 5]) -> HandStrength {
+    let mask = cards.iter().fold(0_u16, |mask, card| mask | 1 << (card >> 2));
+    if mask.count_ones() == 5 {
+        let is_flush = cards.iter().all(|card| card & 3 == cards[0] & 3);
+        if is_flush { table.flush[mask as usize] } else { table.unique[mask as usize] }
+    } else {
+        let product = cards.iter().map(|card| PRIMES[(card >> 2) as usize]).product::<u32>();
+        table.paired[&product]
+    }
+}
+
+fn get_table() -> &'static LookupTable {
+    TABLE.get_or_init(build_table)
+}
+
+//every equivalence class is keyed the same way as HandValue (category then kickers, ace as 14),
+//sorting the keys gives the strength of each class
+fn build_table() -> LookupTable {
+    let mut keys : Vec<(HandCategory, Vec<i16>)> = Vec::new();
+    let mut flush_keys : Vec<(u16, usize)> = Vec::new();
+    let mut unique_keys : Vec<(u16, usize)> = Vec::new();
+    let mut paired_keys : Vec<(u32, usize)> = Vec::new();
+
+    for mask in 0_u16..(1 << 13) {
+        if mask.count_ones() != 5 {
+            continue;
+        }
+        let numbers = (0..13).rev().filter(|rank| mask & (1 << rank) != 0).map(|rank| rank + 2).collect::<Vec<i16>>();
+        let (flush_key, unique_key) = match straight_high(mask) {
+            Some(14) => ((RoyalFlush, vec![14]), (Straight, vec![14])),
+            Some(high) => ((StraightFlush, vec![high]), (Straight, vec![high])),
+            None => ((Flush, numbers.clone()), (HighCard, numbers))
+        };
+        flush_keys.push((mask, keys.len()));
+        keys.push(flush_key);
+        unique_keys.push((mask, keys.len()));
+        keys.push(unique_key);
+    }
+
+    let mut counts = [0_usize; 13];
+    collect_paired(&mut counts, 0, 5, &mut |counts| {
+        let mut groups = (0..13).filter(|&rank| counts[rank] > 0).collect::<Vec<_>>();
+        groups.sort_by(|a, b| counts[*b].cmp(&counts[*a]).then(b.cmp(a)));
+        let pattern = groups.iter().map(|rank| counts[*rank]).collect::<Vec<_>>();
+        let category = match pattern.as_slice() {
+            [4, 1] => FourOfAKind,
+            [3, 2] => FullHouse,
+            [3, 1, 1] => ThreeOfAKind,
+            [2, 2, 1] => TwoPair,
+            [2, 1, 1, 1] => Pair,
+            _ => return
+        };
+        let product = groups.iter().map(|rank| PRIMES[*rank].pow(counts[*rank] as u32)).product::<u32>();
+        paired_keys.push((product, keys.len()));
+        keys.push((category, groups.iter().map(|rank| *rank as i16 + 2).collect()));
+    });
+
+    let mut sorted = keys.clone();
+    sorted.sort();
+    sorted.dedup();
+    let strength_of = |index : usize| sorted.binary_search(&keys[index]).unwrap() as HandStrength + 1;
+
+    let mut flush = vec![0; 1 << 13];
+    let mut unique = vec![0; 1 << 13];
+    let mut paired = HashMap::new();
+    for (mask, index) in flush_keys {
+        flush[mask as usize] = strength_of(index);
+    }
+    for (mask, index) in unique_keys {
+        unique[mask as usize] = strength_of(index);
+    }
+    for (product, index) in paired_keys {
+        paired.insert(product, strength_of(index));
+    }
+    LookupTable {
+        flush,
+        unique,
+        paired,
+        categories : sorted.iter().map(|(category, _)| *category).collect()
+    }
+}
+
+fn straight_high(mask : u16) -> Option<i16> {
+    if mask == WHEEL_MASK {
+        return Some(5);
+    }
+    let low = mask.trailing_zeros();
+    if mask >> low == 0b1_1111 { Some(low as i16 + 6) } else { None }
+}
+
+//every way to put the remaining cards on ranks from `rank` upward with at most four of each rank
+fn collect_paired(counts : &mut [usize; 13], rank : usize, remaining : usize, action : &mut impl FnMut(&[usize; 13])) {
+    if remaining == 0 {
+        action(counts);
+        return;
+    }
+    if rank == 13 {
+        return;
+    }
+    for count in (0..=remaining.min(4)).rev() {
+        counts[rank] = count;
+        collect_paired(counts, rank + 1, remaining - count, action);
+    }
+    counts[rank] = 0;
+}
@@ -0,0 +1,269 @@
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+use crate::entity::card::PokerCard;
+use crate::entity::ruleset::Ruleset;
+use crate::error::{ActionError, PokerError};
+use crate::service::betting_service::{check_raise, check_turn, draw_cards, next_to_act_after, put_raise, settle_by, start_street, take_action, BettingSeat};
+use crate::service::poker_service::{compare_score, score_by_two_deck_with_ruleset, Match};
+pub use crate::service::betting_service::{Action, Settlement};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Street {
+    PreFlop,
+    Flop,
+    Turn,
+    River,
+    Showdown
+}
+
+pub struct Seat {
+    betting : BettingSeat,
+    hole : Vec<Rc<PokerCard>>
+}
+
+pub struct HoldemTable {
+    seats : Vec<Seat>,
+    small_blind : u64,
+    big_blind : u64,
+    button : Option<usize>,
+    poker_match : Option<Match>,
+    street : Street,
+    board : Vec<Rc<PokerCard>>,
+    current_bet : u64,
+    min_raise : u64,
+    to_act : Option<usize>,
+    settlement : Option<Settlement>
+}
+
+impl Seat {
+    fn new(stack : u64) -> Self {
+        Seat {
+            betting : BettingSeat::new(stack),
+            hole : Vec::new()
+        }
+    }
+
+    pub fn get_hole(&self) -> &Vec<Rc<PokerCard>> {
+        &self.hole
+    }
+}
+
+impl Deref for Seat {
+    type Target = BettingSeat;
+
+    fn deref(&self) -> &BettingSeat {
+        &self.betting
+    }
+}
+
+impl DerefMut for Seat {
+    fn deref_mut(&mut self) -> &mut BettingSeat {
+        &mut self.betting
+    }
+}
+
+impl HoldemTable {
+    pub fn new(stacks : Vec<u64>, small_blind : u64, big_blind : u64) -> Self {
+        HoldemTable {
+            seats : stacks.into_iter().map(Seat::new).collect(),
+            small_blind,
+            big_blind,
+            button : None,
+            poker_match : None,
+            street : Street::Showdown,
+            board : Vec::new(),
+            current_bet : 0,
+            min_raise : big_blind,
+            to_act : None,
+            settlement : None
+        }
+    }
+
+    pub fn get_seats(&self) -> &Vec<Seat> {
+        &self.seats
+    }
+
+    pub fn get_button(&self) -> Option<usize> {
+        self.button
+    }
+
+    pub fn get_street(&self) -> Street {
+        self.street
+    }
+
+    pub fn get_board(&self) -> &Vec<Rc<PokerCard>> {
+        &self.board
+    }
+
+    pub fn get_to_act(&self) -> Option<usize> {
+        self.to_act
+    }
+
+    pub fn get_current_bet(&self) -> u64 {
+        self.current_bet
+    }
+
+    pub fn get_min_raise_to(&self) -> u64 {
+        self.current_bet + self.min_raise
+    }
+
+    pub fn get_pot(&self) -> u64 {
+        self.seats.iter().map(|seat| seat.get_total_bet()).sum()
+    }
+
+    pub fn get_call_amount(&self, seat : usize) -> u64 {
+        self.seats.get(seat).map_or(0, |seat| self.current_bet.saturating_sub(seat.get_street_bet()).min(seat.get_stack()))
+    }
+
+    pub fn get_settlement(&self) -> Option<&Settlement> {
+        self.settlement.as_ref()
+    }
+
+    pub fn is_hand_in_progress(&self) -> bool {
+        self.poker_match.is_some()
+    }
+
+    //the match is injected so a seeded match replays the same hand
+    pub fn start_hand(&mut self, poker_match : Match) -> Result<(), PokerError> {
+        if self.is_hand_in_progress() {
+            return Err(ActionError::HandInProgress.into());
+        }
+        let players = self.seats.iter().filter(|seat| seat.get_stack() > 0).count();
+        if players < 2 {
+            return Err(ActionError::NotEnoughPlayers.into());
+        }
+        for seat in self.seats.iter_mut() {
+            *seat = Seat::new(seat.get_stack());
+            seat.sit_in();
+        }
+        let button = match self.button {
+            Some(button) => self.next_seat(button),
+            None => self.next_seat(self.seats.len() - 1)
+        };
+        self.button = Some(button);
+        self.poker_match = Some(poker_match);
+        self.board = Vec::new();
+        self.settlement = None;
+        self.street = Street::PreFlop;
+
+        //heads-up the button posts the small blind and acts first before the flop
+        let small_blind_seat = if players == 2 { button } else { self.next_seat(button) };
+        let big_blind_seat = self.next_seat(small_blind_seat);
+        self.seats[small_blind_seat].put_chips(self.small_blind);
+        self.seats[big_blind_seat].put_chips(self.big_blind);
+        self.current_bet = self.big_blind;
+        self.min_raise = self.big_blind;
+
+        for index in 0..self.seats.len() {
+            if self.seats[index].is_in_hand() {
+                let hole = self.draw(2)?;
+                self.seats[index].hole = hole;
+            }
+        }
+        self.to_act = Some(big_blind_seat);
+        self.to_act = self.next_to_act();
+        if self.to_act.is_none() {
+            self.finish_round()?;
+        }
+        Ok(())
+    }
+
+    pub fn act(&mut self, seat : usize, action : Action) -> Result<(), PokerError> {
+        check_turn(self.is_hand_in_progress(), self.seats.len(), self.to_act, seat)?;
+        if let Some(amount) = take_action(&mut self.seats, seat, action, self.current_bet)? {
+            self.raise_to(seat, amount)?;
+        }
+        self.seats[seat].mark_acted();
+
+        if self.seats.iter().filter(|seat| seat.is_live()).count() == 1 {
+            self.settle()?;
+        } else {
+            self.to_act = next_to_act_after(&self.seats, seat, self.current_bet);
+            if self.to_act.is_none() {
+                self.finish_round()?;
+            }
+        }
+        Ok(())
+    }
+
+    //a raise smaller than the last full raise is only allowed all-in, and it does not reopen
+    //the betting for players who already acted
+    fn raise_to(&mut self, seat : usize, amount : u64) -> Result<(), ActionError> {
+        let all_in = check_raise(&self.seats, seat, amount, self.current_bet)?;
+        let minimum = self.current_bet + self.min_raise;
+        if amount < minimum && !all_in {
+            return Err(if self.current_bet == 0 {
+                ActionError::BetTooSmall { minimum }
+            } else {
+                ActionError::RaiseTooSmall { minimum }
+            });
+        }
+        let full_raise = amount >= minimum;
+        if full_raise {
+            self.min_raise = amount - self.current_bet;
+        }
+        self.current_bet = self.current_bet.max(amount);
+        put_raise(&mut self.seats, seat, amount, full_raise);
+        Ok(())
+    }
+
+    fn next_to_act(&self) -> Option<usize> {
+        self.to_act.and_then(|current| next_to_act_after(&self.seats, current, self.current_bet))
+    }
+
+    fn next_seat(&self, current : usize) -> usize {
+        (1..=self.seats.len())
+            .map(|offset| (current + offset) % self.seats.len())
+            .find(|&index| self.seats[index].is_in_hand())
+            .unwrap_or(current)
+    }
+
+    //deals the following streets until somebody can act again or the river is complete
+    fn finish_round(&mut self) -> Result<(), ActionError> {
+        loop {
+            let dealt = match self.street {
+                Street::PreFlop => 3,
+                Street::Flop | Street::Turn => 1,
+                _ => 0
+            };
+            if dealt == 0 {
+                return self.settle();
+            }
+            self.draw(1)?;
+            let mut cards = self.draw(dealt)?;
+            self.board.append(&mut cards);
+            self.street = match self.street {
+                Street::PreFlop => Street::Flop,
+                Street::Flop => Street::Turn,
+                _ => Street::River
+            };
+            start_street(&mut self.seats);
+            self.current_bet = 0;
+            self.min_raise = self.big_blind;
+            self.to_act = self.button;
+            self.to_act = self.next_to_act();
+            if self.to_act.is_some() {
+                return Ok(());
+            }
+        }
+    }
+
+    fn settle(&mut self) -> Result<(), ActionError> {
+        //the match decides the ruleset, a short deck hand is scored with short deck rankings
+        let ruleset = self.poker_match.as_ref().map_or(Ruleset::Standard, |poker_match| poker_match.get_ruleset());
+        let board = &self.board;
+        let settlement = settle_by(&mut self.seats, self.button.unwrap_or(0), |seat| {
+            let (score, _, _) = score_by_two_deck_with_ruleset(&seat.hole, board, ruleset).map_err(|_| ActionError::ShowdownFailed)?;
+            Ok(score)
+        }, compare_score)?;
+        self.street = Street::Showdown;
+        self.to_act = None;
+        self.poker_match = None;
+        self.settlement = Some(settlement);
+        Ok(())
+    }
+
+    fn draw(&mut self, number : i16) -> Result<Vec<Rc<PokerCard>>, ActionError> {
+        draw_cards(self.poker_match.as_mut(), number)
+    }
+}
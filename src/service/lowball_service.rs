@@ -0,0 +1,149 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use crate::entity::card::PokerCard;
+use crate::error::PokerError;
+use crate::util::poker_util::combinations;
+
+//card numbers from the highest down with ace as 1, so [5, 4, 3, 2, 1] is the best possible low
+pub type LowScore = [i16; 5];
+
+pub type LowHand = (LowScore, [Rc<PokerCard>; 5]);
+
+//slot 0 is the pairing (or made hand) penalty, the rest are card numbers from the most significant down;
+//a smaller score is a better low
+pub type LowballScore = [i16; 6];
+
+//Greater means s1 is the better low, matching compare_score where Greater is the better high
+pub fn compare_low_score(s1 : &LowScore, s2 : &LowScore) -> Ordering {
+    s2.cmp(s1)
+}
+
+pub fn compare_lowball_score(s1 : &LowballScore, s2 : &LowballScore) -> Ordering {
+    s2.cmp(s1)
+}
+
+//eight-or-better: five different numbers, all eight or lower, straights and flushes do not count
+pub fn eight_or_better_score(deck : &[Rc<PokerCard>]) -> Option<LowHand> {
+    let mut best : Option<LowHand> = None;
+    for hand in combinations(deck, 5) {
+        let mut numbers = hand.iter().map(|card| card.get_number()).collect::<Vec<_>>();
+        numbers.sort();
+        numbers.dedup();
+        if numbers.len() != 5 || numbers[4] > 8 {
+            continue;
+        }
+        let low = [numbers[4], numbers[3], numbers[2], numbers[1], numbers[0]];
+        if best.as_ref().is_none_or(|(best_low, _)| compare_low_score(&low, best_low) == Ordering::Greater) {
+            best = Some((low, array_init::array_init(|i| Rc::clone(&hand[i]))));
+        }
+    }
+    best
+}
+
+//ace-to-five (Razz): ace is always low, straights and flushes do not count, pairs do
+pub fn ace_to_five_score(deck : &[Rc<PokerCard>]) -> Result<(LowballScore, [Rc<PokerCard>; 5]), PokerError> {
+    best_low(deck, |hand| {
+        let groups = group_numbers(hand, |card| card.get_number());
+        (pairing_penalty(&groups), groups)
+    })
+}
+
+//deuce-to-seven: ace is always high, straights and flushes count against the hand, so 7-5-4-3-2 is the best
+pub fn deuce_to_seven_score(deck : &[Rc<PokerCard>]) -> Result<(LowballScore, [Rc<PokerCard>; 5]), PokerError> {
+    best_low(deck, |hand| {
+        let groups = group_numbers(hand, |card| card.get_poker_number());
+        let is_flush = hand.iter().all(|card| card.get_card_type() == hand[0].get_card_type());
+        let is_straight = groups.len() == 5 && groups[0].0 - groups[4].0 == 4;
+        //same order as the high hand categories: straight 4, flush 5, full house 6, quads 7, straight flush 8
+        let penalty = match (is_straight, is_flush, pairing_penalty(&groups)) {
+            (true, true, _) => 8,
+            (_, _, 5) => 7,
+            (_, _, 4) => 6,
+            (_, true, _) => 5,
+            (true, _, _) => 4,
+            (_, _, penalty) => penalty
+        };
+        (penalty, groups)
+    })
+}
+
+//the biggest subset of the four cards with no repeated suit or number, ace is low;
+//slot 0 is the number of cards missing from a four-card badugi
+pub fn badugi_score(deck : &[Rc<PokerCard>]) -> Result<(LowballScore, Vec<Rc<PokerCard>>), PokerError> {
+    if deck.len() != 4 {
+        return Err(PokerError::InvalidHandSize { expected : "four", actual : deck.len() });
+    }
+    let mut best : Option<(LowballScore, Vec<Rc<PokerCard>>)> = None;
+    for size in (1..=4).rev() {
+        for mut hand in combinations(deck, size) {
+            let mut numbers = hand.iter().map(|card| card.get_number()).collect::<Vec<_>>();
+            numbers.sort();
+            numbers.dedup();
+            let types = hand.iter().map(|card| card.get_card_type()).collect::<HashSet<_>>();
+            if numbers.len() != size || types.len() != size {
+                continue;
+            }
+            let mut score = [0; 6];
+            score[0] = (4 - size) as i16;
+            for (i, number) in numbers.iter().rev().enumerate() {
+                score[i + 1] = *number;
+            }
+            if best.as_ref().is_none_or(|(best_score, _)| compare_lowball_score(&score, best_score) == Ordering::Greater) {
+                hand.sort_by_key(|card| Reverse(card.get_number()));
+                best = Some((score, hand));
+            }
+        }
+        if best.is_some() {
+            break;
+        }
+    }
+    best.ok_or(PokerError::InvalidHandSize { expected : "four", actual : deck.len() })
+}
+
+fn best_low<F>(deck : &[Rc<PokerCard>], evaluate : F) -> Result<(LowballScore, [Rc<PokerCard>; 5]), PokerError>
+    where F : Fn(&[Rc<PokerCard>]) -> (i16, Vec<(i16, Vec<Rc<PokerCard>>)>) {
+    if deck.len() < 5 || deck.len() > 7 {
+        return Err(PokerError::InvalidHandSize { expected : "five to seven", actual : deck.len() });
+    }
+    let mut best : Option<(LowballScore, [Rc<PokerCard>; 5])> = None;
+    for hand in combinations(deck, 5) {
+        let (penalty, groups) = evaluate(&hand);
+        let mut score = [0; 6];
+        score[0] = penalty;
+        let mut chosen_vec : Vec<Rc<PokerCard>> = Vec::new();
+        for (number, cards) in groups.iter() {
+            for card in cards.iter() {
+                score[chosen_vec.len() + 1] = *number;
+                chosen_vec.push(Rc::clone(card));
+            }
+        }
+        if best.as_ref().is_none_or(|(best_score, _)| compare_lowball_score(&score, best_score) == Ordering::Greater) {
+            best = Some((score, array_init::array_init(|i| Rc::clone(&chosen_vec[i]))));
+        }
+    }
+    best.ok_or(PokerError::InvalidHandSize { expected : "five to seven", actual : deck.len() })
+}
+
+//biggest group first, bigger number first among groups of the same size
+fn group_numbers<F : Fn(&PokerCard) -> i16>(hand : &[Rc<PokerCard>], number : F) -> Vec<(i16, Vec<Rc<PokerCard>>)> {
+    let mut map : HashMap<i16, Vec<Rc<PokerCard>>> = HashMap::new();
+    for card in hand.iter() {
+        map.entry(number(card)).or_default().push(Rc::clone(card));
+    }
+    let mut groups = map.into_iter().collect::<Vec<_>>();
+    groups.sort_by(|(a, a_cards), (b, b_cards)| b_cards.len().cmp(&a_cards.len()).then(b.cmp(a)));
+    groups
+}
+
+//no pair 0, pair 1, two pair 2, trips 3, full house 4, quads 5
+fn pairing_penalty(groups : &[(i16, Vec<Rc<PokerCard>>)]) -> i16 {
+    match (groups[0].1.len(), groups.get(1).map_or(0, |(_, cards)| cards.len())) {
+        (4, _) => 5,
+        (3, 2) => 4,
+        (3, _) => 3,
+        (2, 2) => 2,
+        (2, _) => 1,
+        _ => 0
+    }
+}
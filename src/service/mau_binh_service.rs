@@ -0,0 +1,291 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::HashMap;
+use std::rc::Rc;
+use crate::entity::card::PokerCard;
+use crate::entity::card_set::CardSet;
+use crate::entity::hand::HandCategory;
+use crate::entity::hand::HandCategory::*;
+use crate::entity::ruleset::Ruleset;
+use crate::error::PokerError;
+use crate::service::poker_service::{compare_score, fill_score, score_by_one_deck, PokerScore};
+use crate::util::poker_util::combinations;
+
+//declared from the weakest, a special hand wins outright against any normal hand
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub enum MauBinhSpecial {
+    ThreeStraights,
+    ThreeFlushes,
+    SixPairs,
+    Dragon
+}
+
+pub struct MauBinhRow {
+    cards : Vec<Rc<PokerCard>>,
+    score : PokerScore,
+    category : HandCategory
+}
+
+pub struct MauBinhArrangement {
+    front : MauBinhRow,
+    middle : MauBinhRow,
+    back : MauBinhRow
+}
+
+//rows are front, middle, back from the point of view of the first arrangement
+pub struct MauBinhComparison {
+    rows : [Ordering; 3],
+    points : i32
+}
+
+impl MauBinhSpecial {
+    pub fn get_points(&self) -> i32 {
+        match self {
+            MauBinhSpecial::Dragon => 24,
+            _ => 3
+        }
+    }
+}
+
+impl MauBinhRow {
+    fn new(cards : &[Rc<PokerCard>]) -> Result<Self, PokerError> {
+        let (score, category) = if cards.len() == 3 {
+            front_score(cards)
+        } else {
+            let (score, _, category) = score_by_one_deck(cards)?;
+            (score, category)
+        };
+        Ok(MauBinhRow {
+            cards : cards.to_vec(),
+            score,
+            category
+        })
+    }
+
+    pub fn get_cards(&self) -> &Vec<Rc<PokerCard>> {
+        &self.cards
+    }
+
+    pub fn get_score(&self) -> &PokerScore {
+        &self.score
+    }
+
+    pub fn get_category(&self) -> HandCategory {
+        self.category
+    }
+}
+
+impl MauBinhArrangement {
+    pub fn new(front : &[Rc<PokerCard>], middle : &[Rc<PokerCard>], back : &[Rc<PokerCard>]) -> Result<Self, PokerError> {
+        if front.len() != 3 || middle.len() != 5 || back.len() != 5 {
+            return Err(PokerError::InvalidHandSize { expected : "3 front, 5 middle and 5 back", actual : front.len() + middle.len() + back.len() });
+        }
+        CardSet::new_by_cards(&front.iter().chain(middle.iter()).chain(back.iter()).map(Rc::clone).collect::<Vec<_>>())?;
+        Ok(MauBinhArrangement {
+            front : MauBinhRow::new(front)?,
+            middle : MauBinhRow::new(middle)?,
+            back : MauBinhRow::new(back)?
+        })
+    }
+
+    pub fn get_front(&self) -> &MauBinhRow {
+        &self.front
+    }
+
+    pub fn get_middle(&self) -> &MauBinhRow {
+        &self.middle
+    }
+
+    pub fn get_back(&self) -> &MauBinhRow {
+        &self.back
+    }
+
+    pub fn get_rows(&self) -> [&MauBinhRow; 3] {
+        [&self.front, &self.middle, &self.back]
+    }
+
+    //a fouled hand ("binh lung") has a row stronger than the row behind it
+    pub fn is_fouled(&self) -> bool {
+        compare_score(&self.back.score, &self.middle.score) == Ordering::Less
+            || compare_score(&self.middle.score, &self.front.score) == Ordering::Less
+    }
+
+    //what winning each row is worth, 1 unless the row holds a bonus hand
+    pub fn get_royalties(&self) -> [i32; 3] {
+        [royalty(0, self.front.category), royalty(1, self.middle.category), royalty(2, self.back.category)]
+    }
+
+    //dragon and six pairs come with the cards dealt, a fouled arrangement only loses the specials built from its rows
+    pub fn get_special(&self) -> Option<MauBinhSpecial> {
+        let cards = self.get_rows().iter().flat_map(|row| row.cards.iter().map(Rc::clone)).collect::<Vec<_>>();
+        if self.is_fouled() {
+            return dealt_special(&cards);
+        }
+        special(&cards, &self.front.cards, self.middle.category, self.back.category)
+    }
+}
+
+impl MauBinhComparison {
+    pub fn get_rows(&self) -> &[Ordering; 3] {
+        &self.rows
+    }
+
+    //positive when the first arrangement wins
+    pub fn get_points(&self) -> i32 {
+        self.points
+    }
+}
+
+//special hands settle the comparison on their own, fouled or not; otherwise every row won is worth its royalty,
+//a fouled hand loses every row and winning all three rows doubles the points
+pub fn compare_arrangements(first : &MauBinhArrangement, second : &MauBinhArrangement) -> MauBinhComparison {
+    let rows = match (first.is_fouled(), second.is_fouled()) {
+        (true, true) => [Ordering::Equal; 3],
+        (true, false) => [Ordering::Less; 3],
+        (false, true) => [Ordering::Greater; 3],
+        (false, false) => [
+            compare_score(&first.front.score, &second.front.score),
+            compare_score(&first.middle.score, &second.middle.score),
+            compare_score(&first.back.score, &second.back.score)
+        ]
+    };
+    let points = match (first.get_special(), second.get_special()) {
+        (Some(first_special), Some(second_special)) => match first_special.cmp(&second_special) {
+            Ordering::Greater => first_special.get_points(),
+            Ordering::Less => -second_special.get_points(),
+            Ordering::Equal => 0
+        },
+        (Some(first_special), None) => first_special.get_points(),
+        (None, Some(second_special)) => -second_special.get_points(),
+        (None, None) => {
+            let first_royalties = first.get_royalties();
+            let second_royalties = second.get_royalties();
+            let points = rows.iter().enumerate().map(|(index, row)| match row {
+                Ordering::Greater => first_royalties[index],
+                Ordering::Less => -second_royalties[index],
+                Ordering::Equal => 0
+            }).sum::<i32>();
+            if rows.iter().all(|row| *row == Ordering::Greater) || rows.iter().all(|row| *row == Ordering::Less) {
+                points * 2
+            } else {
+                points
+            }
+        }
+    };
+    MauBinhComparison {
+        rows,
+        points
+    }
+}
+
+//tries every split of the 13 cards and keeps the legal one with the best special hand, then the most royalties,
+//then the strongest back, middle and front in that order
+pub fn solve(cards : &[Rc<PokerCard>]) -> Result<MauBinhArrangement, PokerError> {
+    if cards.len() != 13 {
+        return Err(PokerError::InvalidHandSize { expected : "13", actual : cards.len() });
+    }
+    let indexes = (0..cards.len()).collect::<Vec<_>>();
+    let mut five_scores : HashMap<Vec<usize>, (PokerScore, HandCategory)> = HashMap::new();
+    for five in combinations(&indexes, 5) {
+        let deck = five.iter().map(|&index| Rc::clone(&cards[index])).collect::<Vec<_>>();
+        let (score, _, category) = score_by_one_deck(&deck)?;
+        five_scores.insert(five, (score, category));
+    }
+
+    type Key = (Option<MauBinhSpecial>, i32, PokerScore, PokerScore, PokerScore);
+    let mut best : Option<(Key, [Vec<usize>; 3])> = None;
+    for back in combinations(&indexes, 5) {
+        let rest = indexes.iter().filter(|index| !back.contains(index)).copied().collect::<Vec<_>>();
+        let (back_score, back_category) = five_scores[&back];
+        for middle in combinations(&rest, 5) {
+            let (middle_score, middle_category) = five_scores[&middle];
+            if compare_score(&back_score, &middle_score) == Ordering::Less {
+                continue;
+            }
+            let front = rest.iter().filter(|index| !middle.contains(index)).copied().collect::<Vec<_>>();
+            let front_cards = front.iter().map(|&index| Rc::clone(&cards[index])).collect::<Vec<_>>();
+            let (front_score, front_category) = front_score(&front_cards);
+            if compare_score(&middle_score, &front_score) == Ordering::Less {
+                continue;
+            }
+            let key = (
+                special(cards, &front_cards, middle_category, back_category),
+                royalty(0, front_category) + royalty(1, middle_category) + royalty(2, back_category),
+                back_score,
+                middle_score,
+                front_score
+            );
+            if best.as_ref().is_none_or(|(best_key, _)| key > *best_key) {
+                best = Some((key, [front, middle.clone(), back.clone()]));
+            }
+        }
+    }
+    let (_, [front, middle, back]) = best.ok_or(PokerError::InvalidHandSize { expected : "13", actual : cards.len() })?;
+    let to_cards = |row : &Vec<usize>| row.iter().map(|&index| Rc::clone(&cards[index])).collect::<Vec<_>>();
+    MauBinhArrangement::new(&to_cards(&front), &to_cards(&middle), &to_cards(&back))
+}
+
+//the three card front only makes trips, a pair or high card, scored in the same slots as five card hands
+//so rows can be compared with each other
+fn front_score(cards : &[Rc<PokerCard>]) -> (PokerScore, HandCategory) {
+    let mut chosen = cards.to_vec();
+    chosen.sort_by_key(|card| Reverse(card.get_poker_number()));
+    let numbers = chosen.iter().map(|card| card.get_poker_number()).collect::<Vec<_>>();
+    let category = if numbers[0] == numbers[2] {
+        ThreeOfAKind
+    } else if numbers[0] == numbers[1] || numbers[1] == numbers[2] {
+        Pair
+    } else {
+        HighCard
+    };
+    //the pair leads and the kicker follows, as in a five card pair
+    if category == Pair && numbers[0] != numbers[1] {
+        chosen.rotate_left(1);
+    }
+    (fill_score(category, &chosen, Ruleset::Standard), category)
+}
+
+//back: four of a kind 4, straight flush 5; middle: full house 2, four of a kind 8, straight flush 10; front: trips 3
+fn royalty(row : usize, category : HandCategory) -> i32 {
+    match (row, category) {
+        (0, ThreeOfAKind) => 3,
+        (1, FullHouse) => 2,
+        (1, FourOfAKind) => 8,
+        (1, StraightFlush | RoyalFlush) => 10,
+        (2, FourOfAKind) => 4,
+        (2, StraightFlush | RoyalFlush) => 5,
+        _ => 1
+    }
+}
+
+//specials that only depend on the 13 cards, whatever the arrangement
+fn dealt_special(cards : &[Rc<PokerCard>]) -> Option<MauBinhSpecial> {
+    let mut counts : HashMap<i16, usize> = HashMap::new();
+    for card in cards.iter() {
+        *counts.entry(card.get_number()).or_default() += 1;
+    }
+    if counts.len() == 13 {
+        return Some(MauBinhSpecial::Dragon);
+    }
+    if counts.values().map(|count| count / 2).sum::<usize>() >= 6 {
+        return Some(MauBinhSpecial::SixPairs);
+    }
+    None
+}
+
+fn special(cards : &[Rc<PokerCard>], front : &[Rc<PokerCard>], middle : HandCategory, back : HandCategory) -> Option<MauBinhSpecial> {
+    if let Some(dealt) = dealt_special(cards) {
+        return Some(dealt);
+    }
+    let is_flush = |category : HandCategory| matches!(category, Flush | StraightFlush | RoyalFlush);
+    let is_straight = |category : HandCategory| matches!(category, Straight | StraightFlush | RoyalFlush);
+    if is_flush(middle) && is_flush(back) && front.iter().all(|card| card.get_card_type() == front[0].get_card_type()) {
+        return Some(MauBinhSpecial::ThreeFlushes);
+    }
+    let mut numbers = front.iter().map(|card| card.get_poker_number()).collect::<Vec<_>>();
+    numbers.sort();
+    let front_straight = numbers[0] + 1 == numbers[1] && numbers[1] + 1 == numbers[2] || numbers == [2, 3, 14];
+    if is_straight(middle) && is_straight(back) && front_straight {
+        return Some(MauBinhSpecial::ThreeStraights);
+    }
+    None
+}
@@ -1 +1,15 @@
-pub mod poker_service;
\ No newline at end of file
+pub mod poker_service;
+pub mod fast_poker_service;
+pub mod equity_service;
+pub mod simulation_service;
+pub mod outs_service;
+pub mod holdem_service;
+pub mod pot_service;
+pub mod betting_service;
+pub mod omaha_service;
+pub mod lowball_service;
+pub mod stud_service;
+pub mod draw_service;
+pub mod tien_len_service;
+pub mod mau_binh_service;
+pub mod blackjack_service;
\ No newline at end of file
@@ -0,0 +1,178 @@
+use std::cmp::Ordering;
+use std::rc::Rc;
+use crate::entity::card::PokerCard;
+use crate::entity::hand::HandCategory;
+use crate::error::PokerError;
+use crate::service::poker_service::{compare_score, rank_players, score_by_one_deck, PokerScore, ShowdownResult};
+use crate::service::lowball_service::eight_or_better_score;
+use crate::util::poker_util::combinations;
+pub use crate::service::lowball_service::{compare_low_score, LowHand, LowScore};
+
+pub struct OmahaPlayer {
+    index : usize,
+    high_score : PokerScore,
+    high_chosen : [Rc<PokerCard>; 5],
+    high_category : HandCategory,
+    low : Option<LowHand>
+}
+
+pub struct OmahaHiLoResult {
+    players : Vec<OmahaPlayer>,
+    high_winners : Vec<usize>,
+    low_winners : Vec<usize>
+}
+
+impl OmahaPlayer {
+    pub fn get_index(&self) -> usize {
+        self.index
+    }
+
+    pub fn get_high_score(&self) -> &PokerScore {
+        &self.high_score
+    }
+
+    pub fn get_high_chosen(&self) -> &[Rc<PokerCard>; 5] {
+        &self.high_chosen
+    }
+
+    pub fn get_high_category(&self) -> HandCategory {
+        self.high_category
+    }
+
+    pub fn get_low(&self) -> Option<&LowHand> {
+        self.low.as_ref()
+    }
+}
+
+impl OmahaHiLoResult {
+    pub fn get_players(&self) -> &Vec<OmahaPlayer> {
+        &self.players
+    }
+
+    pub fn get_high_winners(&self) -> &Vec<usize> {
+        &self.high_winners
+    }
+
+    //empty when nobody has a qualifying low, the high hand then takes the whole pot
+    pub fn get_low_winners(&self) -> &Vec<usize> {
+        &self.low_winners
+    }
+
+    pub fn is_scoop(&self, index : usize) -> bool {
+        self.high_winners == vec![index] && (self.low_winners.is_empty() || self.low_winners == vec![index])
+    }
+
+    //the odd chip of an uneven split goes to the high half, odd chips inside a half go to the lowest player index first
+    pub fn split_pot(&self, amount : u64) -> Vec<u64> {
+        let mut result = vec![0; self.players.len()];
+        let (high_half, low_half) = if self.low_winners.is_empty() {
+            (amount, 0)
+        } else {
+            (amount - amount / 2, amount / 2)
+        };
+        for (half, winners) in [(high_half, &self.high_winners), (low_half, &self.low_winners)] {
+            if winners.is_empty() {
+                continue;
+            }
+            let share = half / winners.len() as u64;
+            let odd_chips = (half % winners.len() as u64) as usize;
+            for (position, &index) in winners.iter().enumerate() {
+                result[index] += share + if position < odd_chips { 1 } else { 0 };
+            }
+        }
+        result
+    }
+}
+
+//exactly two of the four (PLO4) or five (PLO5) hole cards and exactly three board cards
+pub fn omaha_high_score(player_deck : &[Rc<PokerCard>], general_deck : &[Rc<PokerCard>]) -> Result<(PokerScore, [Rc<PokerCard>; 5], HandCategory), PokerError> {
+    validate(player_deck, general_deck)?;
+    let mut best : Option<(PokerScore, [Rc<PokerCard>; 5], HandCategory)> = None;
+    for deck in omaha_decks(player_deck, general_deck) {
+        let scored = score_by_one_deck(&deck)?;
+        if best.as_ref().is_none_or(|(best_score, _, _)| compare_score(&scored.0, best_score) == Ordering::Greater) {
+            best = Some(scored);
+        }
+    }
+    best.ok_or(PokerError::InvalidHandSize { expected : "four or five hole", actual : player_deck.len() })
+}
+
+//eight-or-better: five different numbers, all eight or lower, straights and flushes do not count
+pub fn omaha_low_score(player_deck : &[Rc<PokerCard>], general_deck : &[Rc<PokerCard>]) -> Result<Option<LowHand>, PokerError> {
+    validate(player_deck, general_deck)?;
+    let mut best : Option<LowHand> = None;
+    for deck in omaha_decks(player_deck, general_deck) {
+        if let Some(low) = eight_or_better_score(&deck) {
+            if best.as_ref().is_none_or(|(best_low, _)| compare_low_score(&low.0, best_low) == Ordering::Greater) {
+                best = Some(low);
+            }
+        }
+    }
+    Ok(best)
+}
+
+pub fn omaha_showdown(player_decks : &[Vec<Rc<PokerCard>>], general_deck : &[Rc<PokerCard>]) -> Result<ShowdownResult, PokerError> {
+    if player_decks.is_empty() {
+        return Err(PokerError::TooFewPlayers { minimum : 1, actual : 0 });
+    }
+    let mut scored = Vec::new();
+    for player_deck in player_decks.iter() {
+        scored.push(omaha_high_score(player_deck, general_deck)?);
+    }
+    Ok(rank_players(scored))
+}
+
+pub fn omaha_hi_lo_showdown(player_decks : &[Vec<Rc<PokerCard>>], general_deck : &[Rc<PokerCard>]) -> Result<OmahaHiLoResult, PokerError> {
+    if player_decks.is_empty() {
+        return Err(PokerError::TooFewPlayers { minimum : 1, actual : 0 });
+    }
+    let mut players : Vec<OmahaPlayer> = Vec::new();
+    for (index, player_deck) in player_decks.iter().enumerate() {
+        let (high_score, high_chosen, high_category) = omaha_high_score(player_deck, general_deck)?;
+        players.push(OmahaPlayer {
+            index,
+            high_score,
+            high_chosen,
+            high_category,
+            low : omaha_low_score(player_deck, general_deck)?
+        });
+    }
+    let best_high = players.iter().map(|player| player.high_score).max_by(compare_score).unwrap();
+    let high_winners = players.iter()
+        .filter(|player| compare_score(&player.high_score, &best_high) == Ordering::Equal)
+        .map(|player| player.index)
+        .collect();
+    let best_low = players.iter().filter_map(|player| player.low.as_ref().map(|(low, _)| *low)).max_by(compare_low_score);
+    let low_winners = match best_low {
+        Some(best_low) => players.iter()
+            .filter(|player| player.low.as_ref().is_some_and(|(low, _)| *low == best_low))
+            .map(|player| player.index)
+            .collect(),
+        None => Vec::new()
+    };
+    Ok(OmahaHiLoResult {
+        players,
+        high_winners,
+        low_winners
+    })
+}
+
+fn validate(player_deck : &[Rc<PokerCard>], general_deck : &[Rc<PokerCard>]) -> Result<(), PokerError> {
+    if player_deck.len() != 4 && player_deck.len() != 5 {
+        return Err(PokerError::InvalidHandSize { expected : "four or five hole", actual : player_deck.len() });
+    }
+    if general_deck.len() < 3 || general_deck.len() > 5 {
+        return Err(PokerError::InvalidHandSize { expected : "three to five board", actual : general_deck.len() });
+    }
+    Ok(())
+}
+
+fn omaha_decks(player_deck : &[Rc<PokerCard>], general_deck : &[Rc<PokerCard>]) -> Vec<Vec<Rc<PokerCard>>> {
+    let mut result = Vec::new();
+    for hole in combinations(player_deck, 2) {
+        for board in combinations(general_deck, 3) {
+            result.push(hole.iter().chain(board.iter()).map(Rc::clone).collect());
+        }
+    }
+    result
+}
@@ -0,0 +1,200 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::rc::Rc;
+use crate::entity::card::PokerCard;
+use crate::entity::card_set::CardSet;
+use crate::entity::hand::{Draw, HandCategory};
+use crate::entity::ruleset::Ruleset;
+use crate::error::PokerError;
+use crate::service::poker_service::{biggest_sequence, card_maps, compare_score, flush, score_by_one_deck_with_ruleset, IncrementalHand};
+use crate::util::poker_util::combinations;
+
+pub struct Out {
+    card : Rc<PokerCard>,
+    category : HandCategory
+}
+
+pub struct OutsResult {
+    category : HandCategory,
+    outs : Vec<Out>,
+    draws : Vec<Draw>,
+    unseen : usize,
+    cards_to_come : usize
+}
+
+impl Out {
+    pub fn get_card(&self) -> &Rc<PokerCard> {
+        &self.card
+    }
+
+    //what the player makes when this card comes
+    pub fn get_category(&self) -> HandCategory {
+        self.category
+    }
+}
+
+impl OutsResult {
+    //the made hand before the next card
+    pub fn get_category(&self) -> HandCategory {
+        self.category
+    }
+
+    pub fn get_outs(&self) -> &Vec<Out> {
+        &self.outs
+    }
+
+    pub fn get_draws(&self) -> &Vec<Draw> {
+        &self.draws
+    }
+
+    pub fn has_draw(&self, draw : Draw) -> bool {
+        self.draws.contains(&draw)
+    }
+
+    pub fn get_unseen(&self) -> usize {
+        self.unseen
+    }
+
+    pub fn get_cards_to_come(&self) -> usize {
+        self.cards_to_come
+    }
+
+    //chance that at least one out comes by the river
+    pub fn get_exact_percent(&self) -> f64 {
+        let missed = (self.unseen - self.outs.len()) as f64;
+        let mut miss_all = 1.0;
+        for i in 0..self.cards_to_come {
+            miss_all *= ((missed - i as f64) / (self.unseen as f64 - i as f64)).max(0.0);
+        }
+        (1.0 - miss_all) * 100.0
+    }
+
+    //outs times 4 on the flop and times 2 on the turn
+    pub fn get_rule_percent(&self) -> f64 {
+        ((self.outs.len() * 2 * self.cards_to_come) as f64).min(100.0)
+    }
+}
+
+pub fn find_outs(player_deck : &[Rc<PokerCard>], general_deck : &[Rc<PokerCard>]) -> Result<OutsResult, PokerError> {
+    find_outs_with_ruleset(player_deck, general_deck, None, Ruleset::Standard)
+}
+
+pub fn find_outs_against(player_deck : &[Rc<PokerCard>], general_deck : &[Rc<PokerCard>], opponent_deck : &[Rc<PokerCard>]) -> Result<OutsResult, PokerError> {
+    find_outs_with_ruleset(player_deck, general_deck, Some(opponent_deck), Ruleset::Standard)
+}
+
+//without an opponent an out lifts the player to a stronger category than both the current hand and the board
+//alone with that card (so a card pairing the board is not an out); against an opponent an out is any card
+//that leaves the player strictly ahead, the opponent's hole cards are not unseen
+pub fn find_outs_with_ruleset(player_deck : &[Rc<PokerCard>],
+                              general_deck : &[Rc<PokerCard>],
+                              opponent_deck : Option<&[Rc<PokerCard>]>,
+                              ruleset : Ruleset) -> Result<OutsResult, PokerError> {
+    if player_deck.len() != 2 {
+        return Err(PokerError::InvalidHandSize { expected : "two hole", actual : player_deck.len() });
+    }
+    if general_deck.len() != 3 && general_deck.len() != 4 {
+        return Err(PokerError::InvalidHandSize { expected : "three or four board", actual : general_deck.len() });
+    }
+    if let Some(deck) = opponent_deck.filter(|deck| deck.len() != 2) {
+        return Err(PokerError::InvalidHandSize { expected : "two hole", actual : deck.len() });
+    }
+    let known = CardSet::new_by_cards(&join(&join(player_deck, general_deck), opponent_deck.unwrap_or(&[])))?;
+    let unseen = PokerCard::get_all_card_by_ruleset(ruleset).into_iter()
+        .filter(|card| card.get_card().is_some_and(|identity| !known.contains(identity)))
+        .map(Rc::new)
+        .collect::<Vec<_>>();
+    let strength = |category : HandCategory| ruleset.get_category_strength(category);
+
+    let hand = join(player_deck, general_deck);
+    let (_, _, category) = score_by_one_deck_with_ruleset(&hand, ruleset)?;
+    let mut outs = Vec::new();
+    for card in unseen.iter() {
+        let next = [Rc::clone(card)];
+        let (score, _, next_category) = score_by_one_deck_with_ruleset(&join(&hand, &next), ruleset)?;
+        let is_out = match opponent_deck {
+            Some(opponent_deck) => {
+                let (opponent_score, _, _) = score_by_one_deck_with_ruleset(&join(&join(opponent_deck, general_deck), &next), ruleset)?;
+                compare_score(&score, &opponent_score) == Ordering::Greater
+            }
+            None => {
+                let mut board = IncrementalHand::new_with_ruleset(ruleset);
+                board.add_cards(&join(general_deck, &next))?;
+                strength(next_category) > strength(category)
+                    && board.get_category().is_none_or(|board_category| strength(next_category) > strength(board_category))
+            }
+        };
+        if is_out {
+            outs.push(Out {
+                card : Rc::clone(card),
+                category : next_category
+            });
+        }
+    }
+    Ok(OutsResult {
+        category,
+        outs,
+        draws : find_draws(player_deck, general_deck, &unseen, ruleset),
+        unseen : unseen.len(),
+        cards_to_come : 5 - general_deck.len()
+    })
+}
+
+//a draw must use a hole card: the extra cards complete the hand but not the board on its own
+fn find_draws(player_deck : &[Rc<PokerCard>], general_deck : &[Rc<PokerCard>], unseen : &[Rc<PokerCard>], ruleset : Ruleset) -> Vec<Draw> {
+    let low_ace = ruleset.get_low_ace_number();
+    let hand = join(player_deck, general_deck);
+    let completes = |extra : &[Rc<PokerCard>], makes : fn(&[Rc<PokerCard>], i16) -> bool| {
+        makes(&join(&hand, extra), low_ace) && !makes(&join(general_deck, extra), low_ace)
+    };
+    let has_flush = makes_flush(&hand, low_ace);
+    let has_straight = makes_straight(&hand, low_ace);
+
+    let is_flush_draw = !has_flush && unseen.iter().any(|card| completes(&[Rc::clone(card)], makes_flush));
+    let straight_numbers = if has_straight {
+        HashSet::new()
+    } else {
+        unseen.iter()
+            .filter(|card| completes(&[Rc::clone(card)], makes_straight))
+            .map(|card| card.get_number())
+            .collect::<HashSet<_>>()
+    };
+
+    let mut draws = Vec::new();
+    if is_flush_draw {
+        draws.push(Draw::FlushDraw);
+    }
+    match straight_numbers.len() {
+        0 => {}
+        1 => draws.push(Draw::Gutshot),
+        _ => draws.push(Draw::OpenEnded)
+    }
+    if is_flush_draw && !straight_numbers.is_empty() {
+        draws.push(Draw::ComboDraw);
+    }
+    if general_deck.len() == 3 {
+        let pairs = combinations(unseen, 2);
+        if !has_flush && !is_flush_draw && pairs.iter().any(|pair| completes(pair, makes_flush)) {
+            draws.push(Draw::BackdoorFlush);
+        }
+        if !has_straight && straight_numbers.is_empty() && pairs.iter().any(|pair| completes(pair, makes_straight)) {
+            draws.push(Draw::BackdoorStraight);
+        }
+    }
+    draws
+}
+
+fn makes_flush(deck : &[Rc<PokerCard>], low_ace : i16) -> bool {
+    let (_, mut type_map, _) = card_maps(deck);
+    flush(&mut type_map, low_ace).is_some()
+}
+
+fn makes_straight(deck : &[Rc<PokerCard>], low_ace : i16) -> bool {
+    let (_, _, deck_set) = card_maps(deck);
+    let mut cards = deck_set.into_iter().collect::<Vec<_>>();
+    biggest_sequence(&mut cards, low_ace).is_some()
+}
+
+fn join(first : &[Rc<PokerCard>], second : &[Rc<PokerCard>]) -> Vec<Rc<PokerCard>> {
+    first.iter().chain(second.iter()).map(Rc::clone).collect()
+}
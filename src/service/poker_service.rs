@@ -1,78 +1,330 @@
-use std::cmp::Ordering;
+use std::cmp::{Ordering, Reverse};
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
+use std::ops::Deref;
 use std::rc::Rc;
-use rand::Rng;
-use rand::rngs::ThreadRng;
+use rand::{Rng, RngCore};
 use crate::entity::card;
-use crate::entity::card::{PokerCard, Type};
-use crate::service::poker_service::Flush::{RoyalFlush, StraightFlush};
-use crate::service::poker_service::Normal::{HighCard, Straight};
-use crate::service::poker_service::SameType::{FourOfAKind, FullHouse, Pair, ThreeOfAKind, TwoPair};
-use std::string::ToString;
-use strum_macros::Display;
-// use rand::Rng;
-
+use crate::entity::card::{Card, PokerCard, Type};
+use crate::entity::card_set::CardSet;
+use crate::entity::deck::{Deck, DeckSpec};
+use crate::entity::hand::{HandCategory, HandValue};
+use crate::entity::hand::HandCategory::*;
+use crate::entity::ruleset::{Ruleset, WildCards};
+use crate::error::PokerError;
+use strum::IntoEnumIterator;
+
+//the seed is only known when the match created its own rng, an injected rng cannot be replayed by the match
 pub struct Match {
-    deck : Vec<PokerCard>
-}
-
-#[derive(Display, Debug)]
-enum Flush {
-    RoyalFlush,
-    StraightFlush,
-    Flush
+    deck : Deck<PokerCard>,
+    dead : CardSet,
+    ruleset : Ruleset
 }
 
-#[derive(Display, Debug)]
-enum SameType {
-    FourOfAKind,
-    FullHouse,
-    ThreeOfAKind,
-    TwoPair,
-    Pair
-}
-
-#[derive(Display, Debug)]
-enum Normal {
-    Straight,
-    HighCard
+impl Default for Match {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Match {
     pub fn new() -> Self{
+        Self::new_with_seed(rand::thread_rng().gen())
+    }
+
+    pub fn new_with_seed(seed : u64) -> Self {
+        Self::new_with_ruleset(Ruleset::Standard, seed)
+    }
+
+    //the deck only holds the cards the ruleset plays with
+    pub fn new_with_ruleset(ruleset : Ruleset, seed : u64) -> Self {
+        Match {
+            deck : Deck::new_with_seed(DeckSpec::new_by_ruleset(ruleset), seed),
+            dead : CardSet::new(),
+            ruleset
+        }
+    }
+
+    pub fn new_with_rng(rng : impl RngCore + 'static) -> Self {
         Match {
-            deck : PokerCard::get_all_card()
+            deck : Deck::new_with_rng(DeckSpec::default(), rng),
+            dead : CardSet::new(),
+            ruleset : Ruleset::Standard
+        }
+    }
+
+    pub fn get_seed(&self) -> Option<u64> {
+        self.deck.get_seed()
+    }
+
+    pub fn get_ruleset(&self) -> Ruleset {
+        self.ruleset
+    }
+
+    pub fn get_deck(&self) -> &Vec<PokerCard> {
+        self.deck.get_cards()
+    }
+
+    pub fn get_muck(&self) -> &Vec<PokerCard> {
+        self.deck.get_muck()
+    }
+
+    //cards dealt or removed and not discarded back since
+    pub fn get_dead_cards(&self) -> CardSet {
+        self.dead
+    }
+
+    //discarded cards wait in the muck until the stub runs out
+    pub fn discard<C: Deref<Target = PokerCard>>(&mut self, cards : &[C]) {
+        for card in cards.iter().filter_map(|card| card.get_card()) {
+            self.dead.remove(card);
         }
+        self.deck.discard(cards)
     }
 
-    pub fn draw_player(&mut self) -> Result<Vec<PokerCard>, &str> {
+    //takes already known cards (other hands, board, dead cards) out of the deck
+    pub fn remove_cards<C: Deref<Target = PokerCard>>(&mut self, cards : &[C]) -> Result<(), PokerError> {
+        for card in cards.iter() {
+            if card.get_card().is_some_and(|identity| self.dead.contains(identity)) {
+                return Err(PokerError::DuplicateCard(card.to_string()));
+            }
+            self.deck.remove_cards(&[card.deref()])?;
+            self.dead.extend(card.get_card());
+        }
+        Ok(())
+    }
+
+    pub fn draw_player(&mut self) -> Result<Vec<PokerCard>, PokerError> {
         self.draw(2)
     }
 
-    pub fn draw_general(&mut self) -> Result<Vec<PokerCard>, &str> {
+    pub fn draw_general(&mut self) -> Result<Vec<PokerCard>, PokerError> {
         self.draw(5)
     }
 
-    fn draw(&mut self, draw_number : i16) -> Result<Vec<PokerCard>, &str> {
-        if draw_number as usize > self.deck.len() {
-            return Err("Exceed deck number");
+    pub fn draw(&mut self, draw_number : i16) -> Result<Vec<PokerCard>, PokerError> {
+        let cards = self.deck.draw(draw_number as usize)?;
+        self.dead.extend(cards.iter().filter_map(|card| card.get_card()));
+        Ok(cards)
+    }
+}
+
+pub type PokerScore = [i16;19];
+
+pub struct ShowdownPlayer {
+    index : usize,
+    rank : usize,
+    score : PokerScore,
+    hand_value : HandValue
+}
+
+pub struct ShowdownResult {
+    players : Vec<ShowdownPlayer>,
+    winners : Vec<usize>
+}
+
+impl ShowdownPlayer {
+    pub fn get_index(&self) -> usize {
+        self.index
+    }
+
+    pub fn get_rank(&self) -> usize {
+        self.rank
+    }
+
+    pub fn get_score(&self) -> &PokerScore {
+        &self.score
+    }
+
+    pub fn get_hand_value(&self) -> &HandValue {
+        &self.hand_value
+    }
+
+    pub fn get_chosen(&self) -> &[Rc<PokerCard>; 5] {
+        self.hand_value.get_chosen()
+    }
+
+    pub fn get_category(&self) -> HandCategory {
+        self.hand_value.get_category()
+    }
+
+    pub fn is_winner(&self) -> bool {
+        self.rank == 1
+    }
+}
+
+impl ShowdownResult {
+    pub fn get_players(&self) -> &Vec<ShowdownPlayer> {
+        &self.players
+    }
+
+    pub fn get_winners(&self) -> &Vec<usize> {
+        &self.winners
+    }
+
+    pub fn is_split_pot(&self) -> bool {
+        self.winners.len() > 1
+    }
+}
+
+//counts per number and a number mask per suit are updated as each card arrives, so the best hand is read
+//from them instead of regrouping every card; before five cards it holds the made hand so far (pair, high card)
+pub struct IncrementalHand {
+    ruleset : Ruleset,
+    cards : Vec<Rc<PokerCard>>,
+    seen : CardSet,
+    //indexed by poker number, the ace is 14
+    counts : [u8; 15],
+    type_masks : [u16; 4],
+    best : Option<(PokerScore, Vec<Rc<PokerCard>>, HandCategory)>
+}
+
+impl Default for IncrementalHand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IncrementalHand {
+    pub fn new() -> Self {
+        Self::new_with_ruleset(Ruleset::Standard)
+    }
+
+    pub fn new_with_ruleset(ruleset : Ruleset) -> Self {
+        IncrementalHand {
+            ruleset,
+            cards : Vec::new(),
+            seen : CardSet::new(),
+            counts : [0; 15],
+            type_masks : [0; 4],
+            best : None
         }
-        let mut rng = rand::thread_rng();
-        let mut result : Vec<PokerCard> = Vec::new();
-        for _i in 0..draw_number {
-            result.push(self.each_draw(&mut rng));
+    }
+
+    pub fn get_ruleset(&self) -> Ruleset {
+        self.ruleset
+    }
+
+    pub fn get_cards(&self) -> &Vec<Rc<PokerCard>> {
+        &self.cards
+    }
+
+    pub fn get_score(&self) -> Option<&PokerScore> {
+        self.best.as_ref().map(|(score, _, _)| score)
+    }
+
+    //up to five cards, fewer while the hand is still partial
+    pub fn get_chosen(&self) -> &[Rc<PokerCard>] {
+        self.best.as_ref().map_or(&[], |(_, chosen, _)| chosen)
+    }
+
+    pub fn get_category(&self) -> Option<HandCategory> {
+        self.best.as_ref().map(|(_, _, category)| *category)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.cards.len() >= 5
+    }
+
+    pub fn add_card(&mut self, card : Rc<PokerCard>) -> Result<(), PokerError> {
+        self.add_cards(&[card])
+    }
+
+    //nothing is added when one of the cards is rejected
+    pub fn add_cards(&mut self, cards : &[Rc<PokerCard>]) -> Result<(), PokerError> {
+        let mut seen = self.seen;
+        for card in cards.iter() {
+            let identity = card.get_card()
+                .filter(|_| self.ruleset.is_in_deck(card.get_number()))
+                .ok_or_else(|| PokerError::InvalidCard(card.to_string()))?;
+            if !seen.insert(identity) {
+                return Err(PokerError::DuplicateCard(card.to_string()));
+            }
+        }
+        self.seen = seen;
+        for card in cards.iter() {
+            let number = card.get_poker_number();
+            self.counts[number as usize] += 1;
+            self.type_masks[card.get_card_type() as usize] |= 1 << number;
+            self.cards.push(Rc::clone(card));
         }
-        Ok(result)
+        self.best = self.evaluate();
+        Ok(())
     }
 
-    fn each_draw(&mut self, rng: &mut ThreadRng) -> PokerCard {
-        let index = rng.gen_range(0..self.deck.len());
-        self.deck.remove(index)
+    fn evaluate(&self) -> Option<(PokerScore, Vec<Rc<PokerCard>>, HandCategory)> {
+        let mut candidates = vec![self.grouped()];
+        let all_mask = self.type_masks.iter().fold(0, |all, mask| all | mask);
+        if let Some(top) = self.straight_top(all_mask) {
+            candidates.push((Straight, self.pick_sequence(top, None)));
+        }
+        for card_type in Type::iter() {
+            let mask = self.type_masks[card_type as usize];
+            if let Some(top) = self.straight_top(mask) {
+                candidates.push((if top == 14 { RoyalFlush } else { StraightFlush }, self.pick_sequence(top, Some(card_type))));
+            } else if mask.count_ones() >= 5 {
+                let numbers = (2..=14).rev().filter(|number| mask & (1 << number) != 0).take(5);
+                candidates.push((Flush, numbers.flat_map(|number| self.pick(number, Some(card_type), 1)).collect()));
+            }
+        }
+        candidates.into_iter()
+            .filter(|(_, chosen)| !chosen.is_empty())
+            .map(|(category, chosen)| (fill_score(category, &chosen, self.ruleset), chosen, category))
+            .max_by(|(s1, _, _), (s2, _, _)| compare_score(s1, s2))
+    }
+
+    //same grouping as four_three_two_case, falling back to high card and keeping fewer than five cards
+    fn grouped(&self) -> (HandCategory, Vec<Rc<PokerCard>>) {
+        let mut numbers = (2..=14_i16).filter(|&number| self.counts[number as usize] > 0).collect::<Vec<_>>();
+        numbers.sort_by(|a, b| self.counts[*b as usize].cmp(&self.counts[*a as usize]).then(b.cmp(a)));
+        let count = |position : usize| numbers.get(position).map_or(0, |number| self.counts[*number as usize]);
+        let (category, group_sizes) = match (count(0), count(1)) {
+            (4, _) => (FourOfAKind, vec![4]),
+            (3, 2) | (3, 3) => (FullHouse, vec![3, 2]),
+            (3, _) => (ThreeOfAKind, vec![3]),
+            (2, 2) => (TwoPair, vec![2, 2]),
+            (2, _) => (Pair, vec![2]),
+            _ => (HighCard, vec![])
+        };
+        let mut chosen = Vec::new();
+        for (i, size) in group_sizes.iter().enumerate() {
+            chosen.extend(self.pick(numbers[i], None, *size));
+        }
+        let mut kicker_numbers = numbers[group_sizes.len()..].to_vec();
+        kicker_numbers.sort();
+        kicker_numbers.reverse();
+        for number in kicker_numbers.iter().take(5 - chosen.len()) {
+            chosen.extend(self.pick(*number, None, 1));
+        }
+        (category, chosen)
+    }
+
+    //the ace also sits on the low_ace bit so it can start a straight
+    fn straight_top(&self, mask : u16) -> Option<i16> {
+        let low_ace = self.ruleset.get_low_ace_number();
+        let mask = if mask & (1 << 14) != 0 { mask | 1 << low_ace } else { mask };
+        (low_ace + 4..=14).rev().find(|top| {
+            let window = 0b1_1111_u16 << (top - 4);
+            mask & window == window
+        })
     }
-}
 
-type PokerScore = [i16;18];
+    //top card first, the low ace last
+    fn pick_sequence(&self, top : i16, card_type : Option<Type>) -> Vec<Rc<PokerCard>> {
+        let low_ace = self.ruleset.get_low_ace_number();
+        ((top - 4)..=top).rev()
+            .flat_map(|number| self.pick(if number == low_ace { 14 } else { number }, card_type, 1))
+            .collect()
+    }
+
+    fn pick(&self, number : i16, card_type : Option<Type>, size : usize) -> Vec<Rc<PokerCard>> {
+        self.cards.iter()
+            .filter(|card| card.get_poker_number() == number && card_type.is_none_or(|card_type| card.get_card_type() == card_type))
+            .take(size)
+            .map(Rc::clone)
+            .collect()
+    }
+}
 
 pub fn compare_score(s1 : &PokerScore, s2 : &PokerScore) -> Ordering {
     for i in 0..s1.len() {
@@ -85,19 +337,151 @@ pub fn compare_score(s1 : &PokerScore, s2 : &PokerScore) -> Ordering {
     Ordering::Equal
 }
 
-pub fn score_by_one_deck<'a>(deck: &Vec<Rc<PokerCard>>) -> Result<(PokerScore, [Rc<PokerCard>; 5], String), &'a str> {
-    score(deck)
+pub fn score_by_one_deck(deck: &[Rc<PokerCard>]) -> Result<(PokerScore, [Rc<PokerCard>; 5], HandCategory), PokerError> {
+    score(deck, Ruleset::Standard)
 }
 
-pub fn score_by_two_deck<'a>(player_deck : &Vec<Rc<PokerCard>>, general_deck: &Vec<Rc<PokerCard>>) -> Result<(PokerScore, [Rc<PokerCard>; 5], String), &'a str> {
+pub fn score_by_two_deck(player_deck : &[Rc<PokerCard>], general_deck: &[Rc<PokerCard>]) -> Result<(PokerScore, [Rc<PokerCard>; 5], HandCategory), PokerError> {
+    score_by_two_deck_with_ruleset(player_deck, general_deck, Ruleset::Standard)
+}
+
+//scores of different rulesets use different slot layouts, only compare scores of the same ruleset
+pub fn score_by_one_deck_with_ruleset(deck: &[Rc<PokerCard>], ruleset : Ruleset) -> Result<(PokerScore, [Rc<PokerCard>; 5], HandCategory), PokerError> {
+    score(deck, ruleset)
+}
+
+pub fn score_by_two_deck_with_ruleset(player_deck : &[Rc<PokerCard>], general_deck: &[Rc<PokerCard>], ruleset : Ruleset) -> Result<(PokerScore, [Rc<PokerCard>; 5], HandCategory), PokerError> {
     let deck = get_full_deck(vec!(player_deck, general_deck));
-    score(&deck)
+    score(&deck, ruleset)
+}
+
+//wild cards play as whatever makes the best hand; the chosen cards show what they played as
+pub fn score_by_one_deck_with_wild_cards(deck: &[Rc<PokerCard>], wild_cards : &WildCards, ruleset : Ruleset) -> Result<(PokerScore, [Rc<PokerCard>; 5], HandCategory), PokerError> {
+    wild_score(deck, wild_cards, ruleset)
 }
 
-fn score<'a>(deck: &Vec<Rc<PokerCard>>) -> Result<(PokerScore, [Rc<PokerCard>; 5], String), &'a str> {
+pub fn score_by_two_deck_with_wild_cards(player_deck : &[Rc<PokerCard>], general_deck: &[Rc<PokerCard>], wild_cards : &WildCards, ruleset : Ruleset) -> Result<(PokerScore, [Rc<PokerCard>; 5], HandCategory), PokerError> {
+    let deck = get_full_deck(vec!(player_deck, general_deck));
+    wild_score(&deck, wild_cards, ruleset)
+}
+
+//Card is Copy and Send, so these can be called from worker threads; the Rc handles never leave the call
+pub fn score_by_cards(deck : &[Card]) -> Result<(PokerScore, [Card; 5], HandCategory), PokerError> {
+    score_by_cards_with_ruleset(deck, Ruleset::Standard)
+}
+
+//the chosen cards are looked up in the caller's cards, the evaluator may hand back an ace built as 14
+pub fn score_by_cards_with_ruleset(deck : &[Card], ruleset : Ruleset) -> Result<(PokerScore, [Card; 5], HandCategory), PokerError> {
+    let poker_deck = deck.iter().map(|card| Rc::new(card.to_poker_card())).collect::<Vec<_>>();
+    let (score, chosen, category) = score(&poker_deck, ruleset)?;
+    let to_card = |chosen : Rc<PokerCard>| *deck.iter()
+        .find(|card| card.get_card_type() == chosen.get_card_type() && card.get_poker_number() == chosen.get_poker_number())
+        .unwrap();
+    Ok((score, chosen.map(to_card), category))
+}
+
+//one to four cards, e.g. hole cards before the flop; the slots of the missing cards stay 0,
+//so only compare partial scores of the same number of cards
+pub fn score_partial(deck : &[Rc<PokerCard>]) -> Result<(PokerScore, Vec<Rc<PokerCard>>, HandCategory), PokerError> {
+    score_partial_with_ruleset(deck, Ruleset::Standard)
+}
+
+pub fn score_partial_with_ruleset(deck : &[Rc<PokerCard>], ruleset : Ruleset) -> Result<(PokerScore, Vec<Rc<PokerCard>>, HandCategory), PokerError> {
+    if deck.is_empty() {
+        return Err(PokerError::TooFewCards { minimum : 1, actual : 0 });
+    }
+    if deck.len() > 4 {
+        return Err(PokerError::TooManyCards { maximum : 4, actual : deck.len() });
+    }
+    let mut hand = IncrementalHand::new_with_ruleset(ruleset);
+    hand.add_cards(deck)?;
+    hand.best.ok_or(PokerError::TooFewCards { minimum : 1, actual : 0 })
+}
+
+pub fn hand_value_by_one_deck(deck: &[Rc<PokerCard>]) -> Result<HandValue, PokerError> {
+    let (score, chosen, category) = score(deck, Ruleset::Standard)?;
+    Ok(to_hand_value(&score, chosen, category, Ruleset::Standard))
+}
+
+pub fn hand_value_by_two_deck(player_deck : &[Rc<PokerCard>], general_deck: &[Rc<PokerCard>]) -> Result<HandValue, PokerError> {
+    hand_value_by_two_deck_with_ruleset(player_deck, general_deck, Ruleset::Standard)
+}
+
+pub fn hand_value_by_two_deck_with_ruleset(player_deck : &[Rc<PokerCard>], general_deck: &[Rc<PokerCard>], ruleset : Ruleset) -> Result<HandValue, PokerError> {
+    let (score, chosen, category) = score_by_two_deck_with_ruleset(player_deck, general_deck, ruleset)?;
+    Ok(to_hand_value(&score, chosen, category, ruleset))
+}
+
+//only the slots of the hand category are filled, so the non-zero slots are exactly the tie-break numbers
+fn to_hand_value(score : &PokerScore, chosen : [Rc<PokerCard>; 5], category : HandCategory, ruleset : Ruleset) -> HandValue {
+    let kickers = score.iter().filter(|&&number| number != 0).copied().collect::<Vec<_>>();
+    HandValue::new_with_ruleset(category, kickers, chosen, ruleset)
+}
+
+//rank 1 is the best hand, players with exactly the same score share the same rank
+pub fn showdown(player_decks : &[Vec<Rc<PokerCard>>], general_deck : &[Rc<PokerCard>]) -> Result<ShowdownResult, PokerError> {
+    showdown_with_ruleset(player_decks, general_deck, Ruleset::Standard)
+}
+
+pub fn showdown_with_ruleset(player_decks : &[Vec<Rc<PokerCard>>], general_deck : &[Rc<PokerCard>], ruleset : Ruleset) -> Result<ShowdownResult, PokerError> {
+    if player_decks.is_empty() {
+        return Err(PokerError::TooFewPlayers { minimum : 1, actual : 0 });
+    }
+    let mut scored = Vec::new();
+    for player_deck in player_decks.iter() {
+        scored.push(score_by_two_deck_with_ruleset(player_deck, general_deck, ruleset)?);
+    }
+    Ok(rank_players_with_ruleset(scored, ruleset))
+}
+
+//shared by every game that ends in a high-hand showdown, whatever way each player's five cards were chosen
+pub fn rank_players(scored : Vec<(PokerScore, [Rc<PokerCard>; 5], HandCategory)>) -> ShowdownResult {
+    rank_players_with_ruleset(scored, Ruleset::Standard)
+}
+
+pub fn rank_players_with_ruleset(scored : Vec<(PokerScore, [Rc<PokerCard>; 5], HandCategory)>, ruleset : Ruleset) -> ShowdownResult {
+    let ranks = scored.iter()
+        .map(|(score, _, _)| 1 + scored.iter()
+            .filter(|(other, _, _)| compare_score(other, score) == Ordering::Greater)
+            .count())
+        .collect::<Vec<_>>();
+    let mut players : Vec<ShowdownPlayer> = Vec::new();
+    let mut winners : Vec<usize> = Vec::new();
+    for (index, (score, chosen, category)) in scored.into_iter().enumerate() {
+        if ranks[index] == 1 {
+            winners.push(index);
+        }
+        players.push(ShowdownPlayer {
+            index,
+            rank : ranks[index],
+            hand_value : to_hand_value(&score, chosen, category, ruleset),
+            score
+        });
+    }
+    ShowdownResult {
+        players,
+        winners
+    }
+}
+
+fn score(deck: &[Rc<PokerCard>], ruleset : Ruleset) -> Result<(PokerScore, [Rc<PokerCard>; 5], HandCategory), PokerError> {
+    validate_deck(deck, ruleset)?;
+    if let Some(joker) = deck.iter().find(|card| card.is_joker()) {
+        return Err(PokerError::InvalidCard(joker.to_string()));
+    }
+    let (number_map, mut type_map, deck_set) = card_maps(deck);
+    inner_score(&number_map, &mut type_map, &deck_set, ruleset)
+}
+
+pub(crate) type NumberMap = HashMap<i16, Vec<Rc<PokerCard>>>;
+pub(crate) type TypeMap = HashMap<card::Type, Vec<Rc<PokerCard>>>;
+
+//the ace is grouped as 14 and also joins its suit and the straight search as a second card numbered 14
+pub(crate) fn card_maps(deck : &[Rc<PokerCard>]) -> (NumberMap, TypeMap, HashSet<Rc<PokerCard>>) {
     let mut number_map : HashMap<i16, Vec<Rc<PokerCard>>> = HashMap::new();
     let mut type_map : HashMap<card::Type, Vec<Rc<PokerCard>>> = HashMap::new();
-    let mut deck_set = deck.iter().map(|card| Rc::clone(card)).collect::<HashSet<_>>();
+    //PokerCard compares and hashes by number only, so the set keeps one card per number for the straight search
+    let mut deck_set = deck.iter().map(Rc::clone).collect::<HashSet<_>>();
     for card in deck.iter() {
         push_value_to_map_vec(&mut type_map, card.get_card_type(), Rc::clone(card));
         if card.get_number() == 1 {
@@ -109,132 +493,278 @@ fn score<'a>(deck: &Vec<Rc<PokerCard>>) -> Result<(PokerScore, [Rc<PokerCard>; 5
             push_value_to_map_vec(&mut number_map, card.get_number(), Rc::clone(card));
         }
     }
-    inner_score(&number_map, &mut type_map, &deck_set)
+    (number_map, type_map, deck_set)
 }
 
-fn inner_score<'a>(number_map : &HashMap<i16, Vec<Rc<PokerCard>>>,
-               type_map : &mut HashMap<card::Type, Vec<Rc<PokerCard>>>,
-               deck_set : &HashSet<Rc<PokerCard>>) -> Result<(PokerScore, [Rc<PokerCard>; 5], String), &'a str>{
-    let mut result = [0;18];
+//each family is solved directly instead of trying every substitution: groups take the wilds on their numbers
+//(a bug always joins the aces), straights and flushes let every wild fill a gap
+fn wild_score(deck: &[Rc<PokerCard>], wild_cards : &WildCards, ruleset : Ruleset) -> Result<(PokerScore, [Rc<PokerCard>; 5], HandCategory), PokerError> {
+    validate_deck(deck, ruleset)?;
+    let (wilds, naturals) : (Vec<_>, Vec<_>) = deck.iter().map(Rc::clone).partition(|card| wild_cards.is_wild(card.get_number()));
+    if wilds.is_empty() {
+        return score(deck, ruleset);
+    }
+    let bugs = wilds.iter().filter(|card| wild_cards.is_bug_card(card.get_number())).count();
+    let low_ace = ruleset.get_low_ace_number();
+    //numbers as poker numbers, the ace as 14
+    let numbers = (2..=14).filter(|&number| ruleset.is_in_deck(if number == 14 { 1 } else { number })).collect::<Vec<_>>();
+    let (category, chosen) = [
+        wild_flush(&naturals, wilds.len(), &numbers, low_ace),
+        wild_groups(&naturals, wilds.len() - bugs, bugs, &numbers),
+        wild_straight(&naturals, wilds.len(), low_ace)
+    ]
+        .into_iter()
+        .flatten()
+        .max_by_key(|(category, _)| ruleset.get_category_strength(*category))
+        .ok_or(PokerError::TooFewCards { minimum : 5, actual : deck.len() })?;
+    Ok((fill_score(category, &chosen, ruleset), chosen, category))
+}
 
-    let flush_result = flush(type_map);
+//the card a wild plays as
+fn substitute(card_type : Type, number : i16) -> Rc<PokerCard> {
+    Rc::new(PokerCard::new_by_attribute(card_type, if number == 14 { 1 } else { number }))
+}
 
-    if let Some((flush_type, chosen)) = &flush_result {
-        if let RoyalFlush | StraightFlush = flush_type {
-            result[0] = chosen[0].get_number();
-            let mut chosen_copy : [Rc<PokerCard>; 5] = array_init::array_init(|_| Rc::clone(&chosen[0]));
-            for i in 0..chosen.len() {
-                chosen_copy[i] = Rc::clone(&chosen[i]);
-            }
-            return Ok((result, chosen_copy, flush_type.to_string()));
-        }
-    }
+//the poker numbers of a straight window from the top down, with the ace at the bottom taking low_ace
+fn straight_window(top : i16) -> Vec<i16> {
+    (top - 4..=top).rev().collect()
+}
 
-    let same_type_result = four_three_two_case(number_map);
+fn sequence_numbers(card : &PokerCard, low_ace : i16) -> Vec<i16> {
+    if card.get_number() == 1 { vec![14, low_ace] } else { vec![card.get_number()] }
+}
 
-    if let Some((same_type_type, chosen)) = &same_type_result {
-        if let FourOfAKind = same_type_type {
-            result[1] = chosen[0].get_number();
-            result[13] = chosen[4].get_number();
-            let mut chosen_copy : [Rc<PokerCard>; 5] = array_init::array_init(|_| Rc::clone(&chosen[0]));
-            for i in 0..chosen.len() {
-                chosen_copy[i] = Rc::clone(&chosen[i]);
-            }
-            return Ok((result, chosen_copy, same_type_type.to_string()));
-        } else if let FullHouse = same_type_type {
-            result[2] = chosen[0].get_number();
-            result[3] = chosen[3].get_number();
-            let mut chosen_copy : [Rc<PokerCard>; 5] = array_init::array_init(|_| Rc::clone(&chosen[0]));
-            for i in 0..chosen.len() {
-                chosen_copy[i] = Rc::clone(&chosen[i]);
-            }
-            return Ok((result, chosen_copy, same_type_type.to_string()));
+//the highest straight the wilds can complete
+fn wild_straight(naturals : &[Rc<PokerCard>], wilds : usize, low_ace : i16) -> Option<(HandCategory, [Rc<PokerCard>; 5])> {
+    let mut by_number : HashMap<i16, Rc<PokerCard>> = HashMap::new();
+    for card in naturals.iter() {
+        for number in sequence_numbers(card, low_ace) {
+            by_number.entry(number).or_insert_with(|| Rc::clone(card));
         }
     }
+    (low_ace + 4..=14).rev()
+        .find(|&top| straight_window(top).iter().filter(|number| !by_number.contains_key(number)).count() <= wilds)
+        .map(|top| {
+            let cards = straight_window(top).iter()
+                .map(|number| by_number.get(number).map(Rc::clone).unwrap_or_else(|| substitute(Type::SPADE, if *number == low_ace { 14 } else { *number })))
+                .collect::<Vec<_>>();
+            (Straight, array_init::array_init(|i| Rc::clone(&cards[i])))
+        })
+}
 
-    if let Some((flush_type, chosen)) = &flush_result {
-        if let Flush::Flush = flush_type {
-            for i in 0..5 {
-                result[i + 4] = chosen[i].get_number();
-            }
-            let mut chosen_copy : [Rc<PokerCard>; 5] = array_init::array_init(|_| Rc::clone(&chosen[0]));
-            for i in 0..chosen.len() {
-                chosen_copy[i] = Rc::clone(&chosen[i]);
+//straight flush when any suit can make one, otherwise the flush whose cards are highest; wilds in a flush
+//take the highest numbers the suit is missing
+fn wild_flush(naturals : &[Rc<PokerCard>], wilds : usize, numbers : &[i16], low_ace : i16) -> Option<(HandCategory, [Rc<PokerCard>; 5])> {
+    let mut best_straight : Option<(i16, Vec<Rc<PokerCard>>)> = None;
+    let mut best_flush : Option<Vec<Rc<PokerCard>>> = None;
+    for card_type in Type::iter() {
+        let suited = naturals.iter().filter(|card| card.get_card_type() == card_type).collect::<Vec<_>>();
+        if suited.len() + wilds < 5 {
+            continue;
+        }
+        let mut by_number : HashMap<i16, Rc<PokerCard>> = HashMap::new();
+        for card in suited.iter() {
+            for number in sequence_numbers(card, low_ace) {
+                by_number.entry(number).or_insert_with(|| Rc::clone(card));
             }
-            return Ok((result, chosen_copy, flush_type.to_string()));
         }
-    }
-
-    let other_result = straight_and_other(deck_set);
-
-    if let Some((other_type, chosen)) = &other_result {
-        if let Straight = other_type {
-            result[9] = chosen[0].get_number();
-            let mut chosen_copy : [Rc<PokerCard>; 5] = array_init::array_init(|_| Rc::clone(&chosen[0]));
-            for i in 0..chosen.len() {
-                chosen_copy[i] = Rc::clone(&chosen[i]);
+        let top = (low_ace + 4..=14).rev()
+            .find(|&top| straight_window(top).iter().filter(|number| !by_number.contains_key(number)).count() <= wilds);
+        if let Some(top) = top {
+            if best_straight.as_ref().is_none_or(|(best_top, _)| top > *best_top) {
+                let cards = straight_window(top).iter()
+                    .map(|number| by_number.get(number).map(Rc::clone).unwrap_or_else(|| substitute(card_type, if *number == low_ace { 14 } else { *number })))
+                    .collect::<Vec<_>>();
+                best_straight = Some((top, cards));
             }
-            return Ok((result, chosen_copy, other_type.to_string()));
+        }
+        let mut cards = suited.iter().map(|card| Rc::clone(card)).collect::<Vec<_>>();
+        let missing = numbers.iter().rev().filter(|number| !suited.iter().any(|card| card.get_poker_number() == **number));
+        cards.extend(missing.take(wilds).map(|number| substitute(card_type, *number)));
+        cards.sort_by_key(|card| Reverse(card.get_poker_number()));
+        cards.truncate(5);
+        let key = |cards : &Vec<Rc<PokerCard>>| cards.iter().map(|card| card.get_poker_number()).collect::<Vec<_>>();
+        if cards.len() == 5 && best_flush.as_ref().is_none_or(|best| key(&cards) > key(best)) {
+            best_flush = Some(cards);
         }
     }
+    match (best_straight, best_flush) {
+        (Some((top, cards)), _) => Some((if top == 14 { RoyalFlush } else { StraightFlush }, array_init::array_init(|i| Rc::clone(&cards[i])))),
+        (None, Some(cards)) => Some((Flush, array_init::array_init(|i| Rc::clone(&cards[i])))),
+        (None, None) => None
+    }
+}
 
-    if let Some((same_type_type, chosen)) = &same_type_result {
-        if let ThreeOfAKind = same_type_type {
-            result[10] = chosen[0].get_number();
-            result[13] = chosen[3].get_number();
-            result[14] = chosen[4].get_number();
-            let mut chosen_copy : [Rc<PokerCard>; 5] = array_init::array_init(|_| Rc::clone(&chosen[0]));
-            for i in 0..chosen.len() {
-                chosen_copy[i] = Rc::clone(&chosen[i]);
+//five of a kind down to high card, the first pattern the wilds can complete is the best one;
+//group numbers are tried from the highest and the kickers are the highest numbers left
+fn wild_groups(naturals : &[Rc<PokerCard>], wilds : usize, bugs : usize, numbers : &[i16]) -> Option<(HandCategory, [Rc<PokerCard>; 5])> {
+    let mut number_map : HashMap<i16, Vec<Rc<PokerCard>>> = HashMap::new();
+    for card in naturals.iter() {
+        push_value_to_map_vec(&mut number_map, card.get_poker_number(), Rc::clone(card));
+    }
+    for _ in 0..bugs {
+        push_value_to_map_vec(&mut number_map, 14, substitute(Type::SPADE, 14));
+    }
+    let count = |number : i16| number_map.get(&number).map_or(0, |cards| cards.len());
+    let patterns : [(HandCategory, &[usize], usize); 7] = [
+        (FiveOfAKind, &[5], 0),
+        (FourOfAKind, &[4], 1),
+        (FullHouse, &[3, 2], 0),
+        (ThreeOfAKind, &[3], 2),
+        (TwoPair, &[2, 2], 1),
+        (Pair, &[2], 3),
+        (HighCard, &[], 5)
+    ];
+    let descending = numbers.iter().rev().copied().collect::<Vec<_>>();
+    for (category, sizes, kickers) in patterns.iter() {
+        let mut group_choices : Vec<Vec<i16>> = vec![Vec::new()];
+        for (index, _) in sizes.iter().enumerate() {
+            group_choices = group_choices.into_iter()
+                .flat_map(|chosen| descending.iter()
+                    //the second pair of two pair is the lower one
+                    .filter(|number| !chosen.contains(number) && (*category != TwoPair || index == 0 || **number < chosen[0]))
+                    .map(|number| [chosen.clone(), vec![*number]].concat())
+                    .collect::<Vec<_>>())
+                .collect();
+        }
+        for groups in group_choices.iter() {
+            let needed = groups.iter().zip(sizes.iter()).map(|(number, size)| size.saturating_sub(count(*number))).sum::<usize>();
+            if needed > wilds {
+                continue;
             }
-            return Ok((result, chosen_copy, same_type_type.to_string()));
-        } else if let TwoPair = same_type_type {
-            result[11] = chosen[0].get_number();
-            result[12] = chosen[2].get_number();
-            result[13] = chosen[4].get_number();
-            let mut chosen_copy : [Rc<PokerCard>; 5] = array_init::array_init(|_| Rc::clone(&chosen[0]));
-            for i in 0..chosen.len() {
-                chosen_copy[i] = Rc::clone(&chosen[i]);
+            let kicker_cards = descending.iter()
+                .filter(|number| !groups.contains(number) && count(**number) > 0)
+                .take(*kickers)
+                .map(|number| Rc::clone(&number_map[number][0]))
+                .collect::<Vec<_>>();
+            if kicker_cards.len() < *kickers {
+                continue;
             }
-            return Ok((result, chosen_copy, same_type_type.to_string()));
-        } else if let Pair = same_type_type {
-            result[12] = chosen[0].get_number();
-            result[13] = chosen[2].get_number();
-            result[14] = chosen[3].get_number();
-            result[15] = chosen[4].get_number();
-            let mut chosen_copy : [Rc<PokerCard>; 5] = array_init::array_init(|_| Rc::clone(&chosen[0]));
-            for i in 0..chosen.len() {
-                chosen_copy[i] = Rc::clone(&chosen[i]);
+            let mut chosen : Vec<Rc<PokerCard>> = Vec::new();
+            for (number, size) in groups.iter().zip(sizes.iter()) {
+                let natural = number_map.get(number).map_or(Vec::new(), |cards| cards.iter().take(*size).map(Rc::clone).collect());
+                let missing = size - natural.len();
+                chosen.extend(natural);
+                chosen.extend((0..missing).map(|_| substitute(Type::SPADE, *number)));
             }
-            return Ok((result, chosen_copy, same_type_type.to_string()));
+            chosen.extend(kicker_cards);
+            return Some((*category, array_init::array_init(|i| Rc::clone(&chosen[i]))));
+        }
+    }
+    None
+}
+
+//five cards at least, each one a real card of the ruleset's deck and none of them twice; jokers are left to
+//the caller and may repeat when several decks are mixed
+fn validate_deck(deck : &[Rc<PokerCard>], ruleset : Ruleset) -> Result<(), PokerError> {
+    if deck.len() < 5 {
+        return Err(PokerError::TooFewCards { minimum : 5, actual : deck.len() });
+    }
+    let mut seen = CardSet::new();
+    for card in deck.iter().filter(|card| !card.is_joker()) {
+        let identity = card.get_card()
+            .filter(|_| ruleset.is_in_deck(card.get_number()))
+            .ok_or_else(|| PokerError::InvalidCard(card.to_string()))?;
+        if !seen.insert(identity) {
+            return Err(PokerError::DuplicateCard(card.to_string()));
         }
     }
+    Ok(())
+}
 
-    if let Some((other_type, chosen)) = &other_result {
-        if let HighCard = other_type {
+//every candidate category is found first, the ruleset decides which one is the strongest
+fn inner_score(number_map : &HashMap<i16, Vec<Rc<PokerCard>>>,
+               type_map : &mut HashMap<card::Type, Vec<Rc<PokerCard>>>,
+               deck_set : &HashSet<Rc<PokerCard>>,
+               ruleset : Ruleset) -> Result<(PokerScore, [Rc<PokerCard>; 5], HandCategory), PokerError>{
+    let low_ace = ruleset.get_low_ace_number();
+    let (category, chosen) = [flush(type_map, low_ace), four_three_two_case(number_map), straight_and_other(deck_set, low_ace)]
+        .into_iter()
+        .flatten()
+        .max_by_key(|(category, _)| ruleset.get_category_strength(*category))
+        .ok_or(PokerError::TooFewCards { minimum : 5, actual : number_map.values().map(Vec::len).sum() })?;
+
+    Ok((fill_score(category, &chosen, ruleset), chosen, category))
+}
+
+//only the slots of the category and its kickers are filled, kickers always sit in 14-18;
+//a partial hand leaves the slots of its missing cards at 0
+pub(crate) fn fill_score(category : HandCategory, chosen : &[Rc<PokerCard>], ruleset : Ruleset) -> PokerScore {
+    let mut result = [0;19];
+    let slot = leading_slot(category, ruleset);
+    let number = |i : usize| chosen.get(i).map_or(0, |card| card.get_poker_number());
+    match category {
+        FiveOfAKind | RoyalFlush | StraightFlush | Straight => {
+            result[slot] = number(0);
+        }
+        FourOfAKind => {
+            result[slot] = number(0);
+            result[14] = number(4);
+        }
+        FullHouse => {
+            result[slot] = number(0);
+            result[slot + 1] = number(3);
+        }
+        Flush | HighCard => {
             for i in 0..5 {
-                result[i + 13] = chosen[i].get_number();
-            }
-            let mut chosen_copy : [Rc<PokerCard>; 5] = array_init::array_init(|_| Rc::clone(&chosen[0]));
-            for i in 0..chosen.len() {
-                chosen_copy[i] = Rc::clone(&chosen[i]);
+                result[slot + i] = number(i);
             }
-            return Ok((result, chosen_copy, other_type.to_string()));
+        }
+        ThreeOfAKind => {
+            result[slot] = number(0);
+            result[14] = number(3);
+            result[15] = number(4);
+        }
+        TwoPair => {
+            result[slot] = number(0);
+            result[slot + 1] = number(2);
+            result[14] = number(4);
+        }
+        Pair => {
+            result[slot] = number(0);
+            result[14] = number(2);
+            result[15] = number(3);
+            result[16] = number(4);
         }
     }
-    Err("Non supported case")
+    result
 }
 
-fn straight_and_other(deck_set : &HashSet<Rc<PokerCard>>) -> Option<(Normal, [Rc<PokerCard>; 5])> {
-    let mut sorted_vec = deck_set.iter().map(|card| Rc::clone(card)).collect::<Vec<_>>();
-    sorted_vec.sort();
-    if let Some(biggest) =  biggest_sequence(&mut sorted_vec) {
+//the first score slot of each category, a smaller slot wins the comparison so stronger categories come first;
+//short deck puts the flush (3-7) before the full house (8-9) and can swap trips and straight
+fn leading_slot(category : HandCategory, ruleset : Ruleset) -> usize {
+    match (ruleset, category) {
+        (_, FiveOfAKind) => 0,
+        (_, RoyalFlush | StraightFlush) => 1,
+        (_, FourOfAKind) => 2,
+        (Ruleset::Standard, FullHouse) => 3,
+        (Ruleset::Standard, Flush) => 5,
+        (Ruleset::ShortDeck { .. }, Flush) => 3,
+        (Ruleset::ShortDeck { .. }, FullHouse) => 8,
+        (Ruleset::ShortDeck { trips_beat_straight : true }, ThreeOfAKind) => 10,
+        (Ruleset::ShortDeck { trips_beat_straight : true }, Straight) => 11,
+        (_, Straight) => 10,
+        (_, ThreeOfAKind) => 11,
+        (_, TwoPair) => 12,
+        (_, Pair) => 13,
+        (_, HighCard) => 14
+    }
+}
+
+fn straight_and_other(deck_set : &HashSet<Rc<PokerCard>>, low_ace : i16) -> Option<(HandCategory, [Rc<PokerCard>; 5])> {
+    let mut sorted_vec = deck_set.iter().map(Rc::clone).collect::<Vec<_>>();
+    if let Some(biggest) =  biggest_sequence(&mut sorted_vec, low_ace) {
         let mut result : [Rc<PokerCard>; 5] = array_init::array_init(|_| Rc::clone(&biggest[0]));
         for i in 0..5 {
             result[i] = Rc::clone(&biggest[4 - i]);
         }
         return Some((Straight, result));
     }
+    //paired hands can have fewer than five different numbers, they never end up as high card
+    if sorted_vec.len() < 5 {
+        return None;
+    }
+    sorted_vec.sort();
     sorted_vec.reverse();
     let mut result : [Rc<PokerCard>; 5] = array_init::array_init(|_| Rc::clone(&sorted_vec[0]));
     for i in 0..5 {
@@ -244,126 +774,47 @@ fn straight_and_other(deck_set : &HashSet<Rc<PokerCard>>) -> Option<(Normal, [Rc
 }
 
 //four-of-a-kind, full-house, three-of-a-kind, two-pair, pair
-fn four_three_two_case(number_map : &HashMap<i16, Vec<Rc<PokerCard>>>) -> Option<(SameType, [Rc<PokerCard>; 5])> {
-    let mut is_four = false;
-    let mut is_three = false;
-    let mut is_two = false;
-    let mut is_two_pair = false;
-    let mut biggest_four = 0;
-    let mut biggest_three = 0;
-    let mut biggest_two_1 = 0;
-    let mut biggest_two_2 = 0;
-    for (key, value) in number_map.iter() {
-        let number = *key;
-        match value.len() {
-            4 => {
-                is_four = true;
-                if number > biggest_four {
-                    biggest_four = number;
-                }
-            }
-            3 => {
-                is_three = true;
-                if number > biggest_three {
-                    biggest_three = number;
-                }
-            }
-            2 => {
-                if is_two {
-                    if is_two_pair {
-                        let mut v = vec![number, biggest_two_1, biggest_two_2];
-                        v.sort();
-                        v.reverse();
-                        biggest_two_1 = v[0];
-                        biggest_two_2 = v[1];
-                    } else {
-                        is_two_pair = true;
-                        let mut v = vec![number, biggest_two_2];
-                        v.sort();
-                        v.reverse();
-                        biggest_two_1 = v[0];
-                        biggest_two_2 = v[1];
-                    }
-                } else {
-                    is_two = true;
-                    biggest_two_2 = number;
-                }
-            }
-            _ => {/*do nothing*/}
-        }
-    }
-
-    return if is_four {
-        let chosen_number_vec = number_map.get(&biggest_four).unwrap();
-        let mut result : [Rc<PokerCard>; 5] = array_init::array_init(|_| Rc::clone(chosen_number_vec.first().unwrap()));
-        for i in 0..chosen_number_vec.len() {
-            result[i] = Rc::clone(&chosen_number_vec[i]);
-        }
-        let next_chosen_number = number_map.keys().filter(|&&key| key != biggest_four).max().unwrap();
-        result[4] = Rc::clone(number_map.get(next_chosen_number).unwrap().first().unwrap());
-        Some((FourOfAKind, result))
-    } else if is_three && is_two {
-        let chosen_three_vec = number_map.get(&biggest_three).unwrap();
-        let chosen_two_vec = number_map.get(&biggest_two_2).unwrap();
-        let mut result : [Rc<PokerCard>; 5] = array_init::array_init(|_| Rc::clone(chosen_three_vec.first().unwrap()));
-        for i in 0..chosen_three_vec.len() {
-            result[i] = Rc::clone(&chosen_three_vec[i]);
-        }
-        for i in 0..chosen_two_vec.len() {
-            result[i + 3] = Rc::clone(&chosen_two_vec[i]);
-        }
-        Some((FullHouse, result))
-    } else if is_three {
-        let chosen_three_vec = number_map.get(&biggest_three).unwrap();
-        let mut result : [Rc<PokerCard>; 5] = array_init::array_init(|_| Rc::clone(chosen_three_vec.first().unwrap()));
-        for i in 0..chosen_three_vec.len() {
-            result[i] = Rc::clone(&chosen_three_vec[i]);
-        }
-        let mut find_max = number_map.keys().filter(|&&key| key != biggest_three).collect::<Vec<_>>();
-        for i in 0..2 {
-            let next_chosen_number = find_max.iter().max().unwrap();
-            result[i + 3] = Rc::clone(number_map.get(next_chosen_number).unwrap().first().unwrap());
-            find_max = find_max.iter().filter(|&&&key| key != **next_chosen_number).map(|&key| key).collect::<Vec<_>>();
-        }
-        Some((ThreeOfAKind, result))
-    } else if is_two_pair {
-        let chosen_vec_1 = number_map.get(&biggest_two_1).unwrap();
-        let chosen_vec_2 = number_map.get(&biggest_two_2).unwrap();
-        let mut result : [Rc<PokerCard>; 5] = array_init::array_init(|_| Rc::clone(chosen_vec_1.first().unwrap()));
-        for i in 0..chosen_vec_1.len() {
-            result[i] = Rc::clone(&chosen_vec_1[i]);
-        }
-        for i in 0..chosen_vec_2.len() {
-            result[i + 2] = Rc::clone(&chosen_vec_2[i]);
-        }
-        let next_chosen_number = number_map.keys()
-            .filter(|&&key| key != biggest_two_1 && key != biggest_two_2)
-            .max()
-            .unwrap();
-        result[4] = Rc::clone(number_map.get(next_chosen_number).unwrap().first().unwrap());
-        Some((TwoPair, result))
-    } else if is_two {
-        let chosen_vec = number_map.get(&biggest_two_2).unwrap();
-        let mut result : [Rc<PokerCard>; 5] = array_init::array_init(|_| Rc::clone(chosen_vec.first().unwrap()));
-        for i in 0..chosen_vec.len() {
-            result[i] = Rc::clone(&chosen_vec[i]);
-        }
-        let mut find_max = number_map.keys().filter(|&&key| key != biggest_two_2).collect::<Vec<_>>();
-        for i in 0..3 {
-            let next_chosen_number = find_max.iter().max().unwrap();
-            result[i + 2] = Rc::clone(number_map.get(next_chosen_number).unwrap().first().unwrap());
-            find_max = find_max.iter().filter(|&&&key| key != **next_chosen_number).map(|&key| key).collect::<Vec<_>>();
-        }
-        Some((Pair, result))
-    } else {
-        None
+fn four_three_two_case(number_map : &HashMap<i16, Vec<Rc<PokerCard>>>) -> Option<(HandCategory, [Rc<PokerCard>; 5])> {
+    //biggest group first, bigger number first among groups of the same size
+    let mut numbers = number_map.keys().copied().collect::<Vec<_>>();
+    numbers.sort_by(|a, b| number_map[b].len().cmp(&number_map[a].len()).then(b.cmp(a)));
+    let first_len = numbers.first().map_or(0, |number| number_map[number].len());
+    let second_len = numbers.get(1).map_or(0, |number| number_map[number].len());
+    let (same_type_type, group_sizes) = match (first_len, second_len) {
+        (4, _) => (FourOfAKind, vec![4]),
+        (3, 2) | (3, 3) => (FullHouse, vec![3, 2]),
+        (3, _) => (ThreeOfAKind, vec![3]),
+        (2, 2) => (TwoPair, vec![2, 2]),
+        (2, _) => (Pair, vec![2]),
+        _ => return None
+    };
+
+    let mut chosen_vec : Vec<Rc<PokerCard>> = Vec::new();
+    for (i, size) in group_sizes.iter().enumerate() {
+        for card in number_map[&numbers[i]].iter().take(*size) {
+            chosen_vec.push(Rc::clone(card));
+        }
     }
+    let mut kicker_numbers = numbers[group_sizes.len()..].to_vec();
+    kicker_numbers.sort();
+    kicker_numbers.reverse();
+    for number in kicker_numbers.iter() {
+        if chosen_vec.len() >= 5 {
+            break;
+        }
+        chosen_vec.push(Rc::clone(number_map[number].first().unwrap()));
+    }
+    if chosen_vec.len() < 5 {
+        return None;
+    }
+    let result : [Rc<PokerCard>; 5] = array_init::array_init(|i| Rc::clone(&chosen_vec[i]));
+    Some((same_type_type, result))
 }
 
 //straight flush, royal flush (which is just special case of straight flush) and flush
-fn flush(type_map : &mut HashMap<card::Type, Vec<Rc<PokerCard>>>) -> Option<(Flush, [Rc<PokerCard>; 5])> {
-    let mut biggest_top = 0 as i16;
-    let mut biggest_normal = 0 as i16;
+pub(crate) fn flush(type_map : &mut HashMap<card::Type, Vec<Rc<PokerCard>>>, low_ace : i16) -> Option<(HandCategory, [Rc<PokerCard>; 5])> {
+    let mut biggest_top = 0_i16;
+    let mut biggest_normal = 0_i16;
     let placeholder = PokerCard::new_by_attribute(Type::SPADE, 1);
     let mut chosen_top : [Rc<PokerCard>; 5] = array_init::array_init(|_| Rc::new(placeholder.get_copy()));
     let mut chosen_normal : [Rc<PokerCard>; 5] = array_init::array_init(|_| Rc::new(placeholder.get_copy()));
@@ -372,35 +823,35 @@ fn flush(type_map : &mut HashMap<card::Type, Vec<Rc<PokerCard>>>) -> Option<(Flu
         let number_vec = value.iter().map(|card| card.get_number()).collect::<Vec<_>>();
         let len = if number_vec.contains(&1) { value.len() - 1 } else { value.len() };
         if len >= 5 {
-            let mut cards = value.iter().map(|card| Rc::clone(card)).collect::<Vec<_>>();
-            cards.sort();
-            let new_big = biggest_sequence(&mut cards);
+            let mut cards = value.iter().map(Rc::clone).collect::<Vec<_>>();
+            let new_big = biggest_sequence(&mut cards, low_ace);
             if let Some(chosen_ones) = new_big {
-                if chosen_ones[0].get_number() > biggest_top {
-                    biggest_top = chosen_ones[0].get_number();
+                if chosen_ones[4].get_number() > biggest_top {
+                    biggest_top = chosen_ones[4].get_number();
                     for i in 0..5 {
                         chosen_top[i] = Rc::clone(&chosen_ones[4 - i]);
                     }
                 }
             }
+            cards.sort();
             cards.reverse();
-            let new_normal = cards.get(0).unwrap().get_number();
+            let new_normal = cards.first().unwrap().get_number();
             if new_normal > biggest_normal {
                 biggest_normal = new_normal;
-                for i in 0..5 {
-                    chosen_normal[i] = Rc::clone(cards.get(i).unwrap());
+                for (i, chosen) in chosen_normal.iter_mut().enumerate() {
+                    *chosen = Rc::clone(cards.get(i).unwrap());
                 }
             }
         }
     }
     if biggest_top > 0 {
-        if chosen_top[0].get_number() == 10 {
+        if chosen_top[0].get_number() == 14 {
             Some((RoyalFlush, chosen_top))
         } else {
             Some((StraightFlush, chosen_top))
         }
     } else if biggest_normal > 0 {
-        Some((Flush::Flush, chosen_normal))
+        Some((Flush, chosen_normal))
     } else {
         None
     }
@@ -408,41 +859,39 @@ fn flush(type_map : &mut HashMap<card::Type, Vec<Rc<PokerCard>>>) -> Option<(Flu
 
 
 
-fn biggest_sequence(cards : &mut Vec<Rc<PokerCard>>) -> Option<[Rc<PokerCard>; 5]>{
+//returns the highest five consecutive numbers in ascending order, ace must be present as both 1 and 14;
+//the ace written as 1 counts as low_ace, so short deck can pass 5 to join it to the six
+pub(crate) fn biggest_sequence(cards : &mut [Rc<PokerCard>], low_ace : i16) -> Option<[Rc<PokerCard>; 5]>{
     if cards.len() < 5 { return None }
-    cards.sort();
-    let mut next = cards.get(0).unwrap().get_number() + 1;
-    let mut result : [Rc<PokerCard>; 5] = array_init::array_init(|_|Rc::clone(cards.get(0).unwrap()));
-    let mut count = 1;
-    let mut biggest = 0 as i16;
-    for i in 1..cards.len() {
-        let card = cards.get(i).unwrap();
-        if card.get_number() == next {
-            result[count] = Rc::clone(card);
-            count = count + 1;
-        } else {
-            result[1] = Rc::clone(card);
-            count = 1;
-        }
-        if count >= 5 {
-            if card.get_number() > biggest {
-                biggest = card.get_number();
+    let sequence_number = |card : &PokerCard| if card.get_number() == 1 { low_ace } else { card.get_number() };
+    cards.sort_by_key(|card| sequence_number(card));
+    let mut run : Vec<Rc<PokerCard>> = Vec::new();
+    let mut result : Option<[Rc<PokerCard>; 5]> = None;
+    for card in cards.iter() {
+        match run.last() {
+            Some(last) if sequence_number(last) == sequence_number(card) => continue,
+            Some(last) if sequence_number(last) + 1 == sequence_number(card) => run.push(Rc::clone(card)),
+            _ => {
+                run.clear();
+                run.push(Rc::clone(card));
             }
-            count = 1;
         }
-        next = card.get_number() + 1;
+        if run.len() >= 5 {
+            let start = run.len() - 5;
+            result = Some(array_init::array_init(|i| Rc::clone(&run[start + i])));
+        }
     }
-    if biggest > 0 { Some(result) } else { None }
+    result
 }
 
-fn get_full_deck(decks : Vec<&Vec<Rc<PokerCard>>>) -> Vec<Rc<PokerCard>>{
+fn get_full_deck(decks : Vec<&[Rc<PokerCard>]>) -> Vec<Rc<PokerCard>>{
     let mut result: Vec<Rc<PokerCard>> = Vec::new();
     for deck in decks.iter() {
         for card in deck.iter() {
             result.push(Rc::clone(card));
         }
     }
-    return result;
+    result
 }
 
 fn push_value_to_map_vec<K: PartialEq + Eq + Hash, V>(map : &mut HashMap<K, Vec<Rc<V>>>, key : K, value: Rc<V>) {
@@ -451,8 +900,6 @@ fn push_value_to_map_vec<K: PartialEq + Eq + Hash, V>(map : &mut HashMap<K, Vec<
         vec.push(value);
         map.insert(key, vec);
     } else {
-        let mut vec : Vec<Rc<V>> = Vec::new();
-        vec.push(value);
-        map.insert(key, vec);
+        map.insert(key, vec![value]);
     }
 }
\ No newline at end of file
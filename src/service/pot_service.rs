@@ -0,0 +1,105 @@
+use std::cmp::Ordering;
+use crate::service::poker_service::{compare_score, PokerScore};
+
+//seats are indexes into the contribution slice the pot was built from
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Pot {
+    amount : u64,
+    eligible : Vec<usize>
+}
+
+impl Pot {
+    pub fn get_amount(&self) -> u64 {
+        self.amount
+    }
+
+    pub fn get_eligible(&self) -> &Vec<usize> {
+        &self.eligible
+    }
+}
+
+//main pot first, then every side pot in the order the all-in amounts go up;
+//folded chips stay in the pots but a folded seat is never eligible
+pub fn build_pots(contributions : &[u64], folded : &[bool]) -> Vec<Pot> {
+    let mut levels = (0..contributions.len())
+        .filter(|&seat| !folded[seat] && contributions[seat] > 0)
+        .map(|seat| contributions[seat])
+        .collect::<Vec<_>>();
+    levels.sort();
+    levels.dedup();
+
+    let mut pots : Vec<Pot> = Vec::new();
+    let mut previous = 0;
+    for (index, &level) in levels.iter().enumerate() {
+        //chips above the biggest live contribution can only come from folded seats, they join the last pot
+        let top = if index == levels.len() - 1 { u64::MAX } else { level };
+        let amount = contributions.iter().map(|&contribution| contribution.min(top).saturating_sub(previous)).sum();
+        let eligible = (0..contributions.len())
+            .filter(|&seat| !folded[seat] && contributions[seat] >= level)
+            .collect::<Vec<_>>();
+        match pots.last_mut() {
+            Some(last) if last.eligible == eligible => last.amount += amount,
+            _ => pots.push(Pot { amount, eligible })
+        }
+        previous = level;
+    }
+    pots
+}
+
+//the best score among the eligible seats, every seat with exactly that score shares the pot
+pub fn pot_winners(pot : &Pot, scores : &[Option<PokerScore>]) -> Vec<usize> {
+    pot_winners_by(pot, scores, compare_score)
+}
+
+//compare returns Greater for the better score, so low games can pass their own comparison
+pub fn pot_winners_by<S, F : Fn(&S, &S) -> Ordering>(pot : &Pot, scores : &[Option<S>], compare : F) -> Vec<usize> {
+    let mut winners : Vec<usize> = Vec::new();
+    for &seat in pot.eligible.iter() {
+        let score = match &scores[seat] {
+            Some(score) => score,
+            None => continue
+        };
+        match winners.first().map(|&best| compare(score, scores[best].as_ref().unwrap())) {
+            None | Some(Ordering::Equal) => winners.push(seat),
+            Some(Ordering::Greater) => winners = vec![seat],
+            Some(Ordering::Less) => {}
+        }
+    }
+    winners
+}
+
+//a pot nobody has a score for (everyone else folded) goes to its eligible seats;
+//odd chips are handed out one by one starting from the first winner left of the button
+pub fn distribute_pots(pots : &[Pot], scores : &[Option<PokerScore>], button : usize) -> Vec<u64> {
+    distribute_pots_by(pots, scores, button, compare_score)
+}
+
+pub fn distribute_pots_by<S, F : Fn(&S, &S) -> Ordering>(pots : &[Pot], scores : &[Option<S>], button : usize, compare : F) -> Vec<u64> {
+    let seat_count = scores.len();
+    let mut winnings = vec![0; seat_count];
+    for pot in pots.iter() {
+        let mut winners = pot_winners_by(pot, scores, &compare);
+        if winners.is_empty() {
+            winners = pot.eligible.clone();
+        }
+        if winners.is_empty() {
+            continue;
+        }
+        winners.sort_by_key(|&seat| (seat + seat_count - button - 1) % seat_count);
+        let share = pot.amount / winners.len() as u64;
+        let odd_chips = (pot.amount % winners.len() as u64) as usize;
+        for (index, &seat) in winners.iter().enumerate() {
+            winnings[seat] += share + if index < odd_chips { 1 } else { 0 };
+        }
+    }
+    winnings
+}
+
+//payouts and the sorted list of every seat winning at least one pot
+pub fn award_pots_by<S, F : Fn(&S, &S) -> Ordering>(pots : &[Pot], scores : &[Option<S>], button : usize, compare : F) -> (Vec<u64>, Vec<usize>) {
+    let winnings = distribute_pots_by(pots, scores, button, &compare);
+    let mut winners = pots.iter().flat_map(|pot| pot_winners_by(pot, scores, &compare)).collect::<Vec<_>>();
+    winners.sort();
+    winners.dedup();
+    (winnings, winners)
+}
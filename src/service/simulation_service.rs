@@ -0,0 +1,69 @@
+use std::ops::Range;
+use std::thread;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+//worker i always plays stream i of the seed, so the same seed and worker count replay exactly
+#[derive(Debug, Clone, Copy)]
+pub struct Simulation {
+    workers : usize,
+    seed : u64
+}
+
+impl Simulation {
+    pub fn new(workers : usize, seed : u64) -> Self {
+        Simulation {
+            workers : workers.max(1),
+            seed
+        }
+    }
+
+    //one worker per core, falls back to a single worker when the core count is unknown
+    pub fn new_with_available_parallelism(seed : u64) -> Self {
+        Self::new(thread::available_parallelism().map_or(1, |count| count.get()), seed)
+    }
+
+    pub fn get_workers(&self) -> usize {
+        self.workers
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn get_worker_rng(&self, worker : usize) -> ChaCha8Rng {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        rng.set_stream(worker as u64);
+        rng
+    }
+
+    //monte carlo: iterations are split as evenly as possible, the first workers take the remainder
+    pub fn get_share(&self, total : u64, worker : usize) -> u64 {
+        let workers = self.workers as u64;
+        total / workers + if (worker as u64) < total % workers { 1 } else { 0 }
+    }
+
+    //enumeration: every worker takes one contiguous block of the items, sized like get_share
+    pub fn get_range(&self, total : u64, worker : usize) -> Range<u64> {
+        let start = (0..worker).map(|before| self.get_share(total, before)).sum::<u64>();
+        start..start + self.get_share(total, worker)
+    }
+
+    //results come back in worker order whatever order the threads finish in
+    pub fn run<T, F>(&self, job : F) -> Vec<T>
+        where T : Send, F : Fn(usize, ChaCha8Rng) -> T + Sync {
+        if self.workers == 1 {
+            return vec![job(0, self.get_worker_rng(0))];
+        }
+        thread::scope(|scope| {
+            let handles = (0..self.workers)
+                .map(|worker| {
+                    let job = &job;
+                    let rng = self.get_worker_rng(worker);
+                    scope.spawn(move || job(worker, rng))
+                })
+                .collect::<Vec<_>>();
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+        })
+    }
+}
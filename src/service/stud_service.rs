@@ -0,0 +1,358 @@
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+use crate::entity::card::{PokerCard, Type};
+use crate::error::{ActionError, PokerError};
+use crate::service::betting_service::{check_turn, draw_cards, fixed_limit_raise_to, needs_to_act, next_to_act_after, settle_by, start_street, take_action, Action, BettingSeat, Settlement};
+use crate::service::lowball_service::{ace_to_five_score, compare_lowball_score};
+use crate::service::poker_service::{compare_score, score_by_one_deck, Match};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum StudGame {
+    SevenCardStud,
+    Razz
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum StudStreet {
+    Third,
+    Fourth,
+    Fifth,
+    Sixth,
+    Seventh,
+    Showdown
+}
+
+pub struct StudSeat {
+    betting : BettingSeat,
+    down_cards : Vec<Rc<PokerCard>>,
+    up_cards : Vec<Rc<PokerCard>>
+}
+
+//there is no button in stud, seat 0 sits on the dealer's left and wins ties for acting first and odd chips
+pub struct StudTable {
+    game : StudGame,
+    seats : Vec<StudSeat>,
+    ante : u64,
+    bring_in : u64,
+    small_bet : u64,
+    big_bet : u64,
+    poker_match : Option<Match>,
+    street : StudStreet,
+    community_cards : Vec<Rc<PokerCard>>,
+    bring_in_seat : Option<usize>,
+    current_bet : u64,
+    bets : u8,
+    to_act : Option<usize>,
+    settlement : Option<Settlement>
+}
+
+impl StudSeat {
+    fn new(stack : u64) -> Self {
+        StudSeat {
+            betting : BettingSeat::new(stack),
+            down_cards : Vec::new(),
+            up_cards : Vec::new()
+        }
+    }
+
+    pub fn get_down_cards(&self) -> &Vec<Rc<PokerCard>> {
+        &self.down_cards
+    }
+
+    pub fn get_up_cards(&self) -> &Vec<Rc<PokerCard>> {
+        &self.up_cards
+    }
+}
+
+impl Deref for StudSeat {
+    type Target = BettingSeat;
+
+    fn deref(&self) -> &BettingSeat {
+        &self.betting
+    }
+}
+
+impl DerefMut for StudSeat {
+    fn deref_mut(&mut self) -> &mut BettingSeat {
+        &mut self.betting
+    }
+}
+
+impl StudTable {
+    pub fn new(game : StudGame, stacks : Vec<u64>, ante : u64, bring_in : u64, small_bet : u64, big_bet : u64) -> Self {
+        StudTable {
+            game,
+            seats : stacks.into_iter().map(StudSeat::new).collect(),
+            ante,
+            bring_in,
+            small_bet,
+            big_bet,
+            poker_match : None,
+            street : StudStreet::Showdown,
+            community_cards : Vec::new(),
+            bring_in_seat : None,
+            current_bet : 0,
+            bets : 0,
+            to_act : None,
+            settlement : None
+        }
+    }
+
+    pub fn get_game(&self) -> StudGame {
+        self.game
+    }
+
+    pub fn get_seats(&self) -> &Vec<StudSeat> {
+        &self.seats
+    }
+
+    pub fn get_street(&self) -> StudStreet {
+        self.street
+    }
+
+    //only dealt when the deck cannot give every live player a card, it plays for everybody
+    pub fn get_community_cards(&self) -> &Vec<Rc<PokerCard>> {
+        &self.community_cards
+    }
+
+    pub fn get_bring_in_seat(&self) -> Option<usize> {
+        self.bring_in_seat
+    }
+
+    pub fn get_to_act(&self) -> Option<usize> {
+        self.to_act
+    }
+
+    pub fn get_current_bet(&self) -> u64 {
+        self.current_bet
+    }
+
+    pub fn get_bet_size(&self) -> u64 {
+        match self.street {
+            StudStreet::Third | StudStreet::Fourth => self.small_bet,
+            _ => self.big_bet
+        }
+    }
+
+    //a bet, a completion of the bring-in or a raise all go to this amount
+    pub fn get_raise_to(&self) -> u64 {
+        let bet_size = self.get_bet_size();
+        if self.current_bet < bet_size { bet_size } else { self.current_bet + bet_size }
+    }
+
+    pub fn get_pot(&self) -> u64 {
+        self.seats.iter().map(|seat| seat.get_total_bet()).sum()
+    }
+
+    pub fn get_call_amount(&self, seat : usize) -> u64 {
+        self.seats.get(seat).map_or(0, |seat| self.current_bet.saturating_sub(seat.get_street_bet()).min(seat.get_stack()))
+    }
+
+    pub fn get_settlement(&self) -> Option<&Settlement> {
+        self.settlement.as_ref()
+    }
+
+    pub fn is_hand_in_progress(&self) -> bool {
+        self.poker_match.is_some()
+    }
+
+    //antes, two down cards and one up card, then the forced bring-in; the bring-in has no option
+    //when everybody just calls it
+    pub fn start_hand(&mut self, poker_match : Match) -> Result<(), PokerError> {
+        if self.is_hand_in_progress() {
+            return Err(ActionError::HandInProgress.into());
+        }
+        if self.seats.iter().filter(|seat| seat.get_stack() > 0).count() < 2 {
+            return Err(ActionError::NotEnoughPlayers.into());
+        }
+        for seat in self.seats.iter_mut() {
+            *seat = StudSeat::new(seat.get_stack());
+            seat.sit_in();
+            if seat.is_in_hand() {
+                seat.post_ante(self.ante);
+            }
+        }
+        self.poker_match = Some(poker_match);
+        self.community_cards = Vec::new();
+        self.settlement = None;
+        self.street = StudStreet::Third;
+        self.current_bet = 0;
+        self.bets = 0;
+
+        for index in 0..self.seats.len() {
+            if self.seats[index].is_in_hand() {
+                self.seats[index].down_cards = self.draw(2)?;
+                self.seats[index].up_cards = self.draw(1)?;
+            }
+        }
+        let bring_in_seat = self.find_bring_in_seat();
+        self.bring_in_seat = Some(bring_in_seat);
+        self.seats[bring_in_seat].put_chips(self.bring_in);
+        self.seats[bring_in_seat].mark_acted();
+        self.current_bet = self.seats[bring_in_seat].get_street_bet();
+
+        self.to_act = next_to_act_after(&self.seats, bring_in_seat, self.current_bet);
+        if self.to_act.is_none() {
+            self.finish_round()?;
+        }
+        Ok(())
+    }
+
+    //an all-in is only for a stack that cannot cover a full bet, the limit fixes every other amount
+    pub fn act(&mut self, seat : usize, action : Action) -> Result<(), PokerError> {
+        check_turn(self.is_hand_in_progress(), self.seats.len(), self.to_act, seat)?;
+        if let Some(amount) = take_action(&mut self.seats, seat, action, self.current_bet)? {
+            let expected = self.get_raise_to();
+            fixed_limit_raise_to(&mut self.seats, seat, amount, expected, &mut self.current_bet, &mut self.bets)?;
+        }
+        self.seats[seat].mark_acted();
+
+        if self.seats.iter().filter(|seat| seat.is_live()).count() == 1 {
+            self.settle()?;
+        } else {
+            self.to_act = next_to_act_after(&self.seats, seat, self.current_bet);
+            if self.to_act.is_none() {
+                self.finish_round()?;
+            }
+        }
+        Ok(())
+    }
+
+    //stud: the lowest up card brings in, clubs lowest then diamonds, hearts, spades;
+    //razz: the highest up card with ace low, the highest suit brings in on a tie
+    fn find_bring_in_seat(&self) -> usize {
+        let keys = self.seats.iter().enumerate()
+            .filter(|(_, seat)| seat.is_in_hand())
+            .map(|(index, seat)| {
+                let card = &seat.up_cards[0];
+                match self.game {
+                    StudGame::SevenCardStud => (index, (-card.get_poker_number(), -suit_rank(card.get_card_type()))),
+                    StudGame::Razz => (index, (card.get_number(), suit_rank(card.get_card_type())))
+                }
+            })
+            .collect::<Vec<_>>();
+        keys.iter().max_by_key(|(_, key)| *key).map(|(index, _)| *index).unwrap()
+    }
+
+    //the best exposed hand acts first from fourth street on, the earliest seat wins a tie
+    fn first_to_act(&self) -> Option<usize> {
+        let mut best : Option<(usize, Vec<i16>)> = None;
+        for (index, seat) in self.seats.iter().enumerate() {
+            if !seat.is_live() {
+                continue;
+            }
+            let key = exposed_key(&seat.up_cards, self.game);
+            let better = match &best {
+                None => true,
+                Some((_, best_key)) => match self.game {
+                    StudGame::SevenCardStud => key > *best_key,
+                    StudGame::Razz => key < *best_key
+                }
+            };
+            if better {
+                best = Some((index, key));
+            }
+        }
+        let (first, _) = best?;
+        if needs_to_act(&self.seats, first, self.current_bet) {
+            Some(first)
+        } else {
+            next_to_act_after(&self.seats, first, self.current_bet)
+        }
+    }
+
+    //deals the following streets until somebody can act again or seventh street is complete;
+    //eight players can run the deck out, then a single community card replaces everybody's next card
+    fn finish_round(&mut self) -> Result<(), ActionError> {
+        loop {
+            self.street = match self.street {
+                StudStreet::Third => StudStreet::Fourth,
+                StudStreet::Fourth => StudStreet::Fifth,
+                StudStreet::Fifth => StudStreet::Sixth,
+                StudStreet::Sixth => StudStreet::Seventh,
+                _ => return self.settle()
+            };
+            let live = (0..self.seats.len()).filter(|&index| self.seats[index].is_live()).collect::<Vec<_>>();
+            let remaining = self.poker_match.as_ref().map_or(0, |poker_match| poker_match.get_deck().len());
+            if remaining < live.len() {
+                let mut cards = self.draw(1)?;
+                self.community_cards.append(&mut cards);
+            } else {
+                for &index in live.iter() {
+                    let mut cards = self.draw(1)?;
+                    if self.street == StudStreet::Seventh {
+                        self.seats[index].down_cards.append(&mut cards);
+                    } else {
+                        self.seats[index].up_cards.append(&mut cards);
+                    }
+                }
+            }
+            start_street(&mut self.seats);
+            self.current_bet = 0;
+            self.bets = 0;
+            self.to_act = self.first_to_act();
+            if self.to_act.is_some() {
+                return Ok(());
+            }
+        }
+    }
+
+    fn settle(&mut self) -> Result<(), ActionError> {
+        //odd chips start from seat 0
+        let button = self.seats.len() - 1;
+        let community_cards = &self.community_cards;
+        let settlement = match self.game {
+            StudGame::SevenCardStud => settle_by(&mut self.seats, button, |seat| {
+                let (score, _, _) = score_by_one_deck(&full_hand(seat, community_cards)).map_err(|_| ActionError::ShowdownFailed)?;
+                Ok(score)
+            }, compare_score)?,
+            StudGame::Razz => settle_by(&mut self.seats, button, |seat| {
+                let (score, _) = ace_to_five_score(&full_hand(seat, community_cards)).map_err(|_| ActionError::ShowdownFailed)?;
+                Ok(score)
+            }, compare_lowball_score)?
+        };
+        self.street = StudStreet::Showdown;
+        self.to_act = None;
+        self.poker_match = None;
+        self.settlement = Some(settlement);
+        Ok(())
+    }
+
+    fn draw(&mut self, number : i16) -> Result<Vec<Rc<PokerCard>>, ActionError> {
+        draw_cards(self.poker_match.as_mut(), number)
+    }
+}
+
+fn full_hand(seat : &StudSeat, community_cards : &[Rc<PokerCard>]) -> Vec<Rc<PokerCard>> {
+    seat.down_cards.iter().chain(seat.up_cards.iter()).chain(community_cards.iter()).map(Rc::clone).collect()
+}
+
+fn suit_rank(card_type : Type) -> i16 {
+    match card_type {
+        Type::CLUB => 0,
+        Type::DIAMOND => 1,
+        Type::HEART => 2,
+        Type::SPADE => 3
+    }
+}
+
+//group sizes then the numbers group by group, so compared as a whole quads > trips > two pair > pair > high card;
+//straights and flushes never count on the up cards, razz counts the ace as 1
+fn exposed_key(cards : &[Rc<PokerCard>], game : StudGame) -> Vec<i16> {
+    let mut numbers = cards.iter()
+        .map(|card| match game {
+            StudGame::SevenCardStud => card.get_poker_number(),
+            StudGame::Razz => card.get_number()
+        })
+        .collect::<Vec<_>>();
+    numbers.sort();
+    let mut groups : Vec<(i16, i16)> = Vec::new();
+    for number in numbers.iter() {
+        match groups.last_mut() {
+            Some((last, size)) if last == number => *size += 1,
+            _ => groups.push((*number, 1))
+        }
+    }
+    groups.sort_by(|(a, a_size), (b, b_size)| b_size.cmp(a_size).then(b.cmp(a)));
+    groups.iter().map(|(_, size)| *size).chain(groups.iter().map(|(number, _)| *number)).collect()
+}
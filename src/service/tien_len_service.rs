@@ -0,0 +1,289 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use crate::entity::card::{SolitaireCard, Type};
+use crate::entity::deck::{Deck, DeckSpec};
+use crate::error::PokerError;
+
+const HAND_SIZE : usize = 13;
+
+//a sequence is three or more consecutive numbers, consecutive pairs ("doi thong") three or more pairs;
+//neither can hold a 2
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TienLenCombination {
+    Single,
+    Pair,
+    Triple,
+    FourOfAKind,
+    Sequence(usize),
+    ConsecutivePairs(usize)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TienLenError {
+    InvalidPlayerCount(usize),
+    InvalidPlayer(usize),
+    NotYourTurn { expected : usize },
+    GameOver,
+    InvalidCombination,
+    CardNotInHand,
+    MustPlayLowestCard,
+    CannotPassWhenLeading,
+    CannotBeat
+}
+
+//cards are sorted from the lowest, the last card decides between combinations of the same kind
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TienLenMove {
+    combination : TienLenCombination,
+    cards : Vec<SolitaireCard>
+}
+
+pub struct TienLenGame {
+    hands : Vec<Vec<SolitaireCard>>,
+    to_act : Option<usize>,
+    last_move : Option<(usize, TienLenMove)>,
+    passed : Vec<bool>,
+    finished : Vec<usize>,
+    opening_card : Option<SolitaireCard>
+}
+
+impl TienLenMove {
+    pub fn new(cards : &[SolitaireCard]) -> Result<Self, PokerError> {
+        let mut cards = cards.to_vec();
+        cards.sort_by_key(tien_len_value);
+        if cards.is_empty() || cards.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(TienLenError::InvalidCombination.into());
+        }
+        let ranks = cards.iter().map(tien_len_rank).collect::<Vec<_>>();
+        let no_two = ranks.iter().all(|&rank| rank < tien_len_rank(&SolitaireCard::new(Type::SPADE, 2)));
+        let same_rank = ranks.iter().all(|&rank| rank == ranks[0]);
+        let combination = match cards.len() {
+            1 => TienLenCombination::Single,
+            2 if same_rank => TienLenCombination::Pair,
+            3 if same_rank => TienLenCombination::Triple,
+            4 if same_rank => TienLenCombination::FourOfAKind,
+            len if len >= 3 && no_two && ranks.windows(2).all(|pair| pair[0] + 1 == pair[1]) => TienLenCombination::Sequence(len),
+            len if len >= 6 && len % 2 == 0 && no_two
+                && ranks.chunks(2).all(|pair| pair[0] == pair[1])
+                && ranks.chunks(2).collect::<Vec<_>>().windows(2).all(|pairs| pairs[0][0] + 1 == pairs[1][0]) => TienLenCombination::ConsecutivePairs(len / 2),
+            _ => return Err(TienLenError::InvalidCombination.into())
+        };
+        Ok(TienLenMove {
+            combination,
+            cards
+        })
+    }
+
+    pub fn get_combination(&self) -> TienLenCombination {
+        self.combination
+    }
+
+    pub fn get_cards(&self) -> &Vec<SolitaireCard> {
+        &self.cards
+    }
+
+    pub fn get_top_card(&self) -> &SolitaireCard {
+        self.cards.last().unwrap()
+    }
+
+    //the same combination with a higher top card, or a chop: three consecutive pairs or four of a kind on a single 2,
+    //four of a kind on a pair of 2s or three consecutive pairs, four consecutive pairs on any of those
+    pub fn beats(&self, other : &TienLenMove) -> bool {
+        use TienLenCombination::*;
+        if self.combination == other.combination {
+            return tien_len_value(self.get_top_card()) > tien_len_value(other.get_top_card());
+        }
+        let two_rank = tien_len_rank(&SolitaireCard::new(Type::SPADE, 2));
+        let is_two = tien_len_rank(other.get_top_card()) == two_rank;
+        match (self.combination, other.combination) {
+            (ConsecutivePairs(3), Single) => is_two,
+            (FourOfAKind, Single | Pair) => is_two,
+            (FourOfAKind, ConsecutivePairs(3)) => true,
+            (ConsecutivePairs(pairs), Single | Pair) if pairs >= 4 => is_two,
+            (ConsecutivePairs(pairs), ConsecutivePairs(3) | FourOfAKind) => pairs >= 4,
+            _ => false
+        }
+    }
+}
+
+impl TienLenGame {
+    //thirteen cards each for two to four players
+    pub fn new_with_seed(players : usize, seed : u64) -> Result<Self, PokerError> {
+        if !(2..=4).contains(&players) {
+            return Err(TienLenError::InvalidPlayerCount(players).into());
+        }
+        let mut deck : Deck<SolitaireCard> = Deck::new_with_seed(DeckSpec::default(), seed);
+        let hands = (0..players).map(|_| deck.draw(HAND_SIZE).unwrap()).collect();
+        Self::new_with_hands(hands)
+    }
+
+    //whoever holds the lowest card dealt (the 3 of spades with four players) opens and has to play it
+    pub fn new_with_hands(hands : Vec<Vec<SolitaireCard>>) -> Result<Self, PokerError> {
+        if !(2..=4).contains(&hands.len()) {
+            return Err(TienLenError::InvalidPlayerCount(hands.len()).into());
+        }
+        let mut hands = hands;
+        for hand in hands.iter_mut() {
+            hand.sort_by_key(tien_len_value);
+        }
+        let opening = hands.iter().enumerate()
+            .filter_map(|(player, hand)| hand.first().map(|card| (player, *card)))
+            .min_by_key(|(_, card)| tien_len_value(card));
+        let player_count = hands.len();
+        Ok(TienLenGame {
+            hands,
+            to_act : opening.map(|(player, _)| player),
+            last_move : None,
+            passed : vec![false; player_count],
+            finished : Vec::new(),
+            opening_card : opening.map(|(_, card)| card)
+        })
+    }
+
+    pub fn get_hands(&self) -> &Vec<Vec<SolitaireCard>> {
+        &self.hands
+    }
+
+    pub fn get_to_act(&self) -> Option<usize> {
+        self.to_act
+    }
+
+    //None when the player to act leads a new round
+    pub fn get_last_move(&self) -> Option<&(usize, TienLenMove)> {
+        self.last_move.as_ref()
+    }
+
+    pub fn has_passed(&self, player : usize) -> bool {
+        self.passed.get(player).copied().unwrap_or(false)
+    }
+
+    //players in the order they went out, the last one is the loser once the game is over
+    pub fn get_finished(&self) -> &Vec<usize> {
+        &self.finished
+    }
+
+    pub fn get_winner(&self) -> Option<usize> {
+        self.finished.first().copied()
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.finished.len() == self.hands.len()
+    }
+
+    pub fn play(&mut self, player : usize, cards : &[SolitaireCard]) -> Result<(), PokerError> {
+        self.check_turn(player)?;
+        let played = TienLenMove::new(cards)?;
+        if played.cards.iter().any(|card| !self.hands[player].contains(card)) {
+            return Err(TienLenError::CardNotInHand.into());
+        }
+        if let Some(opening_card) = self.opening_card {
+            if !played.cards.contains(&opening_card) {
+                return Err(TienLenError::MustPlayLowestCard.into());
+            }
+        }
+        if let Some((_, last)) = &self.last_move {
+            if !played.beats(last) {
+                return Err(TienLenError::CannotBeat.into());
+            }
+        }
+        self.opening_card = None;
+        self.hands[player].retain(|card| !played.cards.contains(card));
+        self.last_move = Some((player, played));
+        if self.hands[player].is_empty() {
+            self.finished.push(player);
+            let remaining = (0..self.hands.len()).filter(|&other| self.is_active(other)).collect::<Vec<_>>();
+            if remaining.len() == 1 {
+                self.finished.push(remaining[0]);
+                self.to_act = None;
+                return Ok(());
+            }
+        }
+        self.advance(player);
+        Ok(())
+    }
+
+    //a player who passes sits out until the round is over
+    pub fn pass(&mut self, player : usize) -> Result<(), PokerError> {
+        self.check_turn(player)?;
+        if self.last_move.is_none() {
+            return Err(TienLenError::CannotPassWhenLeading.into());
+        }
+        self.passed[player] = true;
+        self.advance(player);
+        Ok(())
+    }
+
+    fn check_turn(&self, player : usize) -> Result<(), TienLenError> {
+        if player >= self.hands.len() {
+            return Err(TienLenError::InvalidPlayer(player));
+        }
+        let expected = self.to_act.ok_or(TienLenError::GameOver)?;
+        if player != expected {
+            return Err(TienLenError::NotYourTurn { expected });
+        }
+        Ok(())
+    }
+
+    fn is_active(&self, player : usize) -> bool {
+        !self.hands[player].is_empty()
+    }
+
+    //the round is over when the turn comes back around to whoever played last; they lead again,
+    //or the next player still holding cards does when they just went out
+    fn advance(&mut self, from : usize) {
+        let player_count = self.hands.len();
+        let last_player = self.last_move.as_ref().map(|(player, _)| *player);
+        let next = (1..=player_count)
+            .map(|offset| (from + offset) % player_count)
+            .find(|&player| Some(player) == last_player || (self.is_active(player) && !self.passed[player]));
+        match next {
+            Some(player) if Some(player) != last_player => self.to_act = Some(player),
+            _ => {
+                let leader = last_player.unwrap_or(from);
+                self.passed = vec![false; player_count];
+                self.last_move = None;
+                self.to_act = (0..player_count)
+                    .map(|offset| (leader + offset) % player_count)
+                    .find(|&player| self.is_active(player));
+            }
+        }
+    }
+}
+
+//3 is the lowest rank and 2 the highest
+pub fn tien_len_rank(card : &SolitaireCard) -> i16 {
+    match card.get_number() {
+        1 => 11,
+        2 => 12,
+        number => number - 3
+    }
+}
+
+//spade < club < diamond < heart inside the same rank
+pub fn tien_len_value(card : &SolitaireCard) -> i16 {
+    let suit = match card.get_card_type() {
+        Type::SPADE => 0,
+        Type::CLUB => 1,
+        Type::DIAMOND => 2,
+        Type::HEART => 3
+    };
+    tien_len_rank(card) * 4 + suit
+}
+
+impl Display for TienLenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TienLenError::InvalidPlayerCount(count) => write!(f, "Tien Len needs two to four players, got {}", count),
+            TienLenError::InvalidPlayer(player) => write!(f, "Player {} does not exist", player),
+            TienLenError::NotYourTurn { expected } => write!(f, "Player {} is to act", expected),
+            TienLenError::GameOver => write!(f, "The game is over"),
+            TienLenError::InvalidCombination => write!(f, "Cards do not form a valid combination"),
+            TienLenError::CardNotInHand => write!(f, "Card not in hand"),
+            TienLenError::MustPlayLowestCard => write!(f, "The opening move must include the lowest card"),
+            TienLenError::CannotPassWhenLeading => write!(f, "Cannot pass when leading a round"),
+            TienLenError::CannotBeat => write!(f, "Move does not beat the last move")
+        }
+    }
+}
+
+impl Error for TienLenError {}
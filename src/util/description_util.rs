@@ -0,0 +1,194 @@
+use std::ops::Deref;
+use crate::entity::card::PokerCard;
+use crate::entity::hand::{HandCategory, HandValue};
+use crate::entity::hand::HandCategory::*;
+
+//the numbers a description needs, ace as 14: main names the hand (the pair, both pairs from the higher,
+//trips then pair of a full house, the top card of a straight or flush), kickers are the other cards that count
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HandDescription {
+    category : HandCategory,
+    main : Vec<i16>,
+    kickers : Vec<i16>
+}
+
+//one implementation per language, the client picks which one to render with
+pub trait HandLocale {
+    fn category_name(&self, category : HandCategory) -> String;
+
+    fn describe(&self, description : &HandDescription) -> String;
+}
+
+pub struct English;
+
+pub struct Vietnamese;
+
+impl HandDescription {
+    //numbers in score order: groups first, then kickers, a straight from its top card
+    pub fn new(category : HandCategory, numbers : &[i16]) -> Self {
+        let main_len = match category {
+            RoyalFlush => 0,
+            FullHouse | TwoPair => 2,
+            _ => 1
+        }.min(numbers.len());
+        let kickers = match category {
+            FourOfAKind | ThreeOfAKind | TwoPair | Pair | HighCard => numbers[main_len..].to_vec(),
+            _ => Vec::new()
+        };
+        HandDescription {
+            category,
+            main : numbers[..main_len].to_vec(),
+            kickers
+        }
+    }
+
+    //chosen as the evaluators return them, partial hands with fewer than five cards included
+    pub fn new_by_chosen<C : Deref<Target = PokerCard>>(category : HandCategory, chosen : &[C]) -> Self {
+        let mut numbers = chosen.iter().map(|card| card.get_poker_number()).collect::<Vec<_>>();
+        numbers.dedup();
+        Self::new(category, &numbers)
+    }
+
+    pub fn new_by_hand_value(value : &HandValue) -> Self {
+        Self::new(value.get_category(), value.get_kickers())
+    }
+
+    pub fn get_category(&self) -> HandCategory {
+        self.category
+    }
+
+    pub fn get_main(&self) -> &Vec<i16> {
+        &self.main
+    }
+
+    pub fn get_kickers(&self) -> &Vec<i16> {
+        &self.kickers
+    }
+}
+
+pub fn describe_hand_value(value : &HandValue, locale : &impl HandLocale) -> String {
+    locale.describe(&HandDescription::new_by_hand_value(value))
+}
+
+pub fn describe_chosen<C : Deref<Target = PokerCard>>(category : HandCategory, chosen : &[C], locale : &impl HandLocale) -> String {
+    locale.describe(&HandDescription::new_by_chosen(category, chosen))
+}
+
+//"Two Pair, Kings and Sevens with a Queen kicker", "Straight, Five high", "Royal Flush"
+impl HandLocale for English {
+    fn category_name(&self, category : HandCategory) -> String {
+        match category {
+            HighCard => "High Card",
+            Pair => "Pair",
+            TwoPair => "Two Pair",
+            ThreeOfAKind => "Three of a Kind",
+            Straight => "Straight",
+            Flush => "Flush",
+            FullHouse => "Full House",
+            FourOfAKind => "Four of a Kind",
+            StraightFlush => "Straight Flush",
+            RoyalFlush => "Royal Flush",
+            FiveOfAKind => "Five of a Kind"
+        }.to_string()
+    }
+
+    fn describe(&self, description : &HandDescription) -> String {
+        let name = self.category_name(description.category);
+        let main = |i : usize| description.main.get(i).copied().unwrap_or(0);
+        let head = match (description.category, description.main.len()) {
+            (RoyalFlush, _) | (_, 0) => name,
+            (Pair, _) => format!("Pair of {}", english_plural(main(0))),
+            (TwoPair, 2) => format!("{}, {} and {}", name, english_plural(main(0)), english_plural(main(1))),
+            (FullHouse, 2) => format!("{}, {} full of {}", name, english_plural(main(0)), english_plural(main(1))),
+            (Straight | Flush | StraightFlush, _) => format!("{}, {} high", name, english_name(main(0))),
+            (HighCard, _) => format!("{}, {}", name, english_name(main(0))),
+            _ => format!("{}, {}", name, english_plural(main(0)))
+        };
+        let kickers = description.kickers.iter().map(|number| english_name(*number)).collect::<Vec<_>>();
+        match kickers.as_slice() {
+            [] => head,
+            [kicker] => format!("{} with {} {} kicker", head, if kicker.starts_with(['A', 'E']) { "an" } else { "a" }, kicker),
+            [rest @ .., last] => format!("{} with {} and {} kickers", head, rest.join(", "), last)
+        }
+    }
+}
+
+//"Thú, đôi Già và đôi Bảy, kèm Đầm", "Sảnh, lá cao nhất Năm", "Thùng phá sảnh lớn"
+impl HandLocale for Vietnamese {
+    fn category_name(&self, category : HandCategory) -> String {
+        match category {
+            HighCard => "Mậu thầu",
+            Pair => "Đôi",
+            TwoPair => "Thú",
+            ThreeOfAKind => "Sám cô",
+            Straight => "Sảnh",
+            Flush => "Thùng",
+            FullHouse => "Cù lũ",
+            FourOfAKind => "Tứ quý",
+            StraightFlush => "Thùng phá sảnh",
+            RoyalFlush => "Thùng phá sảnh lớn",
+            FiveOfAKind => "Ngũ quý"
+        }.to_string()
+    }
+
+    fn describe(&self, description : &HandDescription) -> String {
+        let name = self.category_name(description.category);
+        let main = |i : usize| vietnamese_name(description.main.get(i).copied().unwrap_or(0));
+        let head = match (description.category, description.main.len()) {
+            (RoyalFlush, _) | (_, 0) => name,
+            (TwoPair, 2) => format!("{}, đôi {} và đôi {}", name, main(0), main(1)),
+            (FullHouse, 2) => format!("{}, ba lá {} và đôi {}", name, main(0), main(1)),
+            (Straight | Flush | StraightFlush, _) => format!("{}, lá cao nhất {}", name, main(0)),
+            _ => format!("{} {}", name, main(0))
+        };
+        if description.kickers.is_empty() {
+            head
+        } else {
+            let kickers = description.kickers.iter().map(|number| vietnamese_name(*number)).collect::<Vec<_>>();
+            format!("{}, kèm {}", head, kickers.join(", "))
+        }
+    }
+}
+
+fn english_name(number : i16) -> &'static str {
+    match number {
+        2 => "Two",
+        3 => "Three",
+        4 => "Four",
+        5 => "Five",
+        6 => "Six",
+        7 => "Seven",
+        8 => "Eight",
+        9 => "Nine",
+        10 => "Ten",
+        11 => "Jack",
+        12 => "Queen",
+        13 => "King",
+        _ => "Ace"
+    }
+}
+
+fn english_plural(number : i16) -> String {
+    match number {
+        6 => "Sixes".to_string(),
+        _ => format!("{}s", english_name(number))
+    }
+}
+
+fn vietnamese_name(number : i16) -> &'static str {
+    match number {
+        2 => "Hai",
+        3 => "Ba",
+        4 => "Bốn",
+        5 => "Năm",
+        6 => "Sáu",
+        7 => "Bảy",
+        8 => "Tám",
+        9 => "Chín",
+        10 => "Mười",
+        11 => "Bồi",
+        12 => "Đầm",
+        13 => "Già",
+        _ => "Át"
+    }
+}
@@ -1 +1,2 @@
-pub mod poker_util;
\ No newline at end of file
+pub mod poker_util;
+pub mod description_util;
\ No newline at end of file
@@ -1,5 +1,4 @@
 use std::ops::Deref;
-use std::rc::Rc;
 use crate::entity::card::PokerCard;
 
 pub fn to_string_decks(decks : Vec<impl Deref<Target = PokerCard>>) -> String {
@@ -11,4 +10,19 @@ pub fn to_string_decks(decks : Vec<impl Deref<Target = PokerCard>>) -> String {
         }
     }
     result
+}
+
+//every way to pick size items keeping their original order
+pub fn combinations<T : Clone>(items : &[T], size : usize) -> Vec<Vec<T>> {
+    if size == 0 {
+        return vec![Vec::new()];
+    }
+    let mut result = Vec::new();
+    for i in 0..items.len() {
+        for mut rest in combinations(&items[(i + 1)..], size - 1) {
+            rest.insert(0, items[i].clone());
+            result.push(rest);
+        }
+    }
+    result
 }
\ No newline at end of file
@@ -0,0 +1,203 @@
+use poker_core::entity::card::SolitaireCard;
+use poker_core::entity::deck::{Deck, DeckSpec};
+use poker_core::error::PokerError;
+use poker_core::service::blackjack_service::{blackjack_total, xi_dach_special, xi_dach_total, BlackjackAction, BlackjackError, BlackjackPhase, BlackjackTable, BlackjackVariant, HandOutcome};
+
+mod common;
+use common::solitaire_cards;
+
+const S17 : BlackjackVariant = BlackjackVariant::Blackjack { dealer_hits_soft_17 : false };
+const H17 : BlackjackVariant = BlackjackVariant::Blackjack { dealer_hits_soft_17 : true };
+
+fn value(card : &SolitaireCard) -> i16 {
+    card.get_number().min(10)
+}
+
+//one player: the player gets the first and third card, the dealer the second and the hole card
+fn find_shoe(predicate : impl Fn(&[SolitaireCard]) -> bool) -> Deck<SolitaireCard> {
+    for seed in 0..1_000_000 {
+        let shoe = Deck::new_shoe_with_seed(DeckSpec::new_with_decks(6), 0, seed);
+        let next = shoe.peek(8).into_iter().copied().collect::<Vec<_>>();
+        if predicate(&next) {
+            return shoe;
+        }
+    }
+    panic!("no shoe matches");
+}
+
+fn total(first : &SolitaireCard, second : &SolitaireCard) -> i16 {
+    blackjack_total(&[*first, *second]).0
+}
+
+#[test]
+fn totals_count_aces_both_ways() {
+    assert_eq!(blackjack_total(&solitaire_cards("Ah 6d")), (17, true));
+    assert_eq!(blackjack_total(&solitaire_cards("Ah 6d Tc")), (17, false));
+    assert_eq!(blackjack_total(&solitaire_cards("Ah Ad 9c")), (21, true));
+    assert_eq!(blackjack_total(&solitaire_cards("Kh Qd 5c")), (25, false));
+
+    assert_eq!(xi_dach_total(&solitaire_cards("Ah 9d")), 20);
+    assert_eq!(xi_dach_total(&solitaire_cards("Ah 5d 6c")), 21);
+    assert_eq!(xi_dach_total(&solitaire_cards("Ah 5d 8c")), 14);
+    assert_eq!(xi_dach_total(&solitaire_cards("Ah 2d 3c 4s")), 10);
+
+    assert_eq!(xi_dach_special(&solitaire_cards("Ah Ad")), Some(HandOutcome::XiBan));
+    assert_eq!(xi_dach_special(&solitaire_cards("Ah Kd")), Some(HandOutcome::XiDach));
+    assert_eq!(xi_dach_special(&solitaire_cards("2h 3d 4c 5s Ah")), Some(HandOutcome::NguLinh));
+    assert_eq!(xi_dach_special(&solitaire_cards("5h 6d 4c 5s 2h")), None);
+}
+
+#[test]
+fn natural_pays_three_to_two() {
+    let shoe = find_shoe(|next| total(&next[0], &next[2]) == 21 && next[1].get_number() != 1 && total(&next[1], &next[3]) != 21);
+    let mut table = BlackjackTable::new(S17, shoe);
+    table.start_round(&[10]).unwrap();
+    assert_eq!(table.get_phase(), BlackjackPhase::Finished);
+    assert_eq!(table.get_hands()[0].get_outcome(), Some(HandOutcome::Blackjack));
+    assert_eq!(table.get_hands()[0].get_payout(), 15);
+    //nothing to play against, the dealer keeps two cards
+    assert_eq!(table.get_dealer_cards().len(), 2);
+}
+
+#[test]
+fn blackjack_rejects_odd_bets_that_would_pay_half_chips() {
+    let shoe = find_shoe(|next| total(&next[0], &next[2]) == 21 && next[1].get_number() != 1 && total(&next[1], &next[3]) != 21);
+    let mut table = BlackjackTable::new(S17, shoe);
+    assert_eq!(table.start_round(&[10, 7]), Err(PokerError::Blackjack(BlackjackError::OddBet(7))));
+    assert_eq!(table.start_round(&[0]), Err(PokerError::Blackjack(BlackjackError::InvalidBet)));
+    assert!(table.get_hands().is_empty());
+    table.start_round(&[12]).unwrap();
+    assert_eq!(table.get_hands()[0].get_payout(), 18);
+
+    //xi dach never halves a bet
+    let mut table = BlackjackTable::new(BlackjackVariant::XiDach, find_shoe(|_| true));
+    assert!(table.start_round(&[7]).is_ok());
+}
+
+#[test]
+fn insurance_pays_two_to_one_against_a_dealer_natural() {
+    let shoe = find_shoe(|next| next[1].get_number() == 1 && value(&next[3]) == 10 && total(&next[0], &next[2]) != 21);
+    let mut table = BlackjackTable::new(S17, shoe);
+    table.start_round(&[10]).unwrap();
+    assert_eq!(table.get_phase(), BlackjackPhase::Insurance);
+    assert_eq!(table.act(0, BlackjackAction::Hit), Err(PokerError::Blackjack(BlackjackError::IllegalAction(BlackjackAction::Hit))));
+    table.act(0, BlackjackAction::TakeInsurance).unwrap();
+    assert_eq!(table.get_phase(), BlackjackPhase::Finished);
+    let hand = &table.get_hands()[0];
+    assert_eq!(hand.get_insurance(), 5);
+    assert_eq!(hand.get_outcome(), Some(HandOutcome::Lose));
+    assert_eq!(hand.get_payout(), 0);
+}
+
+#[test]
+fn lost_insurance_costs_half_the_bet() {
+    let shoe = || find_shoe(|next| next[1].get_number() == 1 && value(&next[3]) != 10
+        && (17..=20).contains(&total(&next[0], &next[2])) && next[0].get_number() != 1 && next[2].get_number() != 1);
+    let mut declined = BlackjackTable::new(S17, shoe());
+    declined.start_round(&[10]).unwrap();
+    declined.act(0, BlackjackAction::DeclineInsurance).unwrap();
+    declined.act(0, BlackjackAction::Stand).unwrap();
+    let mut taken = BlackjackTable::new(S17, shoe());
+    taken.start_round(&[10]).unwrap();
+    taken.act(0, BlackjackAction::TakeInsurance).unwrap();
+    taken.act(0, BlackjackAction::Stand).unwrap();
+    assert_eq!(taken.get_hands()[0].get_outcome(), declined.get_hands()[0].get_outcome());
+    assert_eq!(taken.get_hands()[0].get_payout(), declined.get_hands()[0].get_payout() - 5);
+}
+
+#[test]
+fn dealer_hits_soft_seventeen_only_under_h17() {
+    let shoe = || find_shoe(|next| next[1].get_number() == 6 && next[3].get_number() == 1 && total(&next[0], &next[2]) == 20);
+    let mut stands = BlackjackTable::new(S17, shoe());
+    stands.start_round(&[10]).unwrap();
+    stands.act(0, BlackjackAction::Stand).unwrap();
+    assert_eq!(stands.get_dealer_cards().len(), 2);
+    assert_eq!(stands.get_hands()[0].get_outcome(), Some(HandOutcome::Win));
+    assert_eq!(stands.get_hands()[0].get_payout(), 10);
+
+    let mut hits = BlackjackTable::new(H17, shoe());
+    hits.start_round(&[10]).unwrap();
+    hits.act(0, BlackjackAction::Stand).unwrap();
+    assert!(hits.get_dealer_cards().len() > 2);
+    let (dealer_total, soft) = blackjack_total(hits.get_dealer_cards());
+    assert!(dealer_total > 17 || (dealer_total == 17 && !soft));
+}
+
+#[test]
+fn busted_hand_loses_without_the_dealer_drawing() {
+    let shoe = find_shoe(|next| next[1].get_number() != 1 && total(&next[1], &next[3]) < 17
+        && total(&next[0], &next[2]) == 16 && next[0].get_number() != 1 && next[2].get_number() != 1 && value(&next[4]) == 10);
+    let mut table = BlackjackTable::new(S17, shoe);
+    table.start_round(&[10]).unwrap();
+    table.act(0, BlackjackAction::Hit).unwrap();
+    assert_eq!(table.get_phase(), BlackjackPhase::Finished);
+    assert_eq!(table.get_hands()[0].get_outcome(), Some(HandOutcome::Bust));
+    assert_eq!(table.get_hands()[0].get_payout(), -10);
+    assert_eq!(table.get_dealer_cards().len(), 2);
+}
+
+#[test]
+fn double_doubles_the_bet_and_takes_one_card() {
+    let shoe = find_shoe(|next| next[1].get_number() != 1 && total(&next[1], &next[3]) != 21 && total(&next[0], &next[2]) == 11);
+    let mut table = BlackjackTable::new(S17, shoe);
+    table.start_round(&[10]).unwrap();
+    table.act(0, BlackjackAction::Double).unwrap();
+    assert_eq!(table.get_phase(), BlackjackPhase::Finished);
+    let hand = &table.get_hands()[0];
+    assert!(hand.is_doubled());
+    assert_eq!(hand.get_bet(), 20);
+    assert_eq!(hand.get_cards().len(), 3);
+    let expected = match hand.get_outcome() {
+        Some(HandOutcome::Win) => 20,
+        Some(HandOutcome::Push) => 0,
+        _ => -20
+    };
+    assert_eq!(hand.get_payout(), expected);
+}
+
+#[test]
+fn split_aces_take_one_card_and_twenty_one_pays_even_money() {
+    let shoe = find_shoe(|next| next[0].get_number() == 1 && next[2].get_number() == 1
+        && next[1].get_number() != 1 && total(&next[1], &next[3]) == 20 && value(&next[4]) == 10 && value(&next[5]) < 9);
+    let mut table = BlackjackTable::new(S17, shoe);
+    table.start_round(&[10]).unwrap();
+    table.act(0, BlackjackAction::Split).unwrap();
+    assert_eq!(table.get_phase(), BlackjackPhase::Finished);
+    let hands = table.get_hands();
+    assert_eq!(hands.len(), 2);
+    assert!(hands.iter().all(|hand| hand.is_split() && hand.get_cards().len() == 2 && hand.get_player() == 0));
+    assert_eq!(hands[0].get_outcome(), Some(HandOutcome::Win));
+    assert_eq!(hands[0].get_payout(), 10);
+    assert_eq!(hands[1].get_outcome(), Some(HandOutcome::Lose));
+    assert_eq!(hands[1].get_payout(), -10);
+}
+
+#[test]
+fn xi_ban_pays_three_times_the_bet() {
+    let shoe = find_shoe(|next| next[0].get_number() == 1 && next[2].get_number() == 1 && xi_dach_special(&[next[1], next[3]]).is_none());
+    let mut table = BlackjackTable::new(BlackjackVariant::XiDach, shoe);
+    table.start_round(&[10]).unwrap();
+    assert_eq!(table.get_phase(), BlackjackPhase::Finished);
+    assert_eq!(table.get_hands()[0].get_outcome(), Some(HandOutcome::XiBan));
+    assert_eq!(table.get_hands()[0].get_payout(), 30);
+}
+
+#[test]
+fn dealer_xi_dach_takes_twice_the_bet() {
+    let shoe = find_shoe(|next| xi_dach_special(&[next[1], next[3]]) == Some(HandOutcome::XiDach) && xi_dach_special(&[next[0], next[2]]).is_none());
+    let mut table = BlackjackTable::new(BlackjackVariant::XiDach, shoe);
+    table.start_round(&[10]).unwrap();
+    assert_eq!(table.get_phase(), BlackjackPhase::Finished);
+    assert_eq!(table.get_hands()[0].get_outcome(), Some(HandOutcome::Lose));
+    assert_eq!(table.get_hands()[0].get_payout(), -20);
+}
+
+#[test]
+fn xi_dach_players_need_sixteen_to_stand() {
+    let shoe = find_shoe(|next| xi_dach_special(&[next[1], next[3]]).is_none() && xi_dach_total(&[next[0], next[2]]) < 16);
+    let mut table = BlackjackTable::new(BlackjackVariant::XiDach, shoe);
+    table.start_round(&[10]).unwrap();
+    assert_eq!(table.act(0, BlackjackAction::Stand), Err(PokerError::Blackjack(BlackjackError::NotEnoughPoints { minimum : 16 })));
+    assert_eq!(table.act(0, BlackjackAction::Double), Err(PokerError::Blackjack(BlackjackError::IllegalAction(BlackjackAction::Double))));
+    assert_eq!(table.get_phase(), BlackjackPhase::PlayerTurns);
+}
@@ -0,0 +1,22 @@
+use poker_core::entity::card::{ParseCardError, PokerCard, PokerDeck, Type};
+
+#[test]
+fn card_notation_round_trips() {
+    let card = "Td".parse::<PokerCard>().unwrap();
+    assert_eq!((card.get_card_type(), card.get_number()), (Type::DIAMOND, 10));
+    assert_eq!(card.to_string(), "Td");
+    let deck = "As Kd,Qh  2c".parse::<PokerDeck>().unwrap();
+    assert_eq!(deck.to_string(), "As Kd Qh 2c");
+    assert_eq!("AsKd7h".parse::<PokerDeck>().unwrap().get_cards().len(), 3);
+}
+
+#[test]
+fn card_notation_errors() {
+    assert_eq!("A".parse::<PokerCard>(), Err(ParseCardError::InvalidLength("A".to_string())));
+    assert_eq!("10s".parse::<PokerCard>(), Err(ParseCardError::InvalidLength("10s".to_string())));
+    assert_eq!("1s".parse::<PokerCard>(), Err(ParseCardError::InvalidRank('1')));
+    assert_eq!("Az".parse::<PokerCard>(), Err(ParseCardError::InvalidSuit('z')));
+    assert_eq!("As Kd A".parse::<PokerDeck>().err(), Some(ParseCardError::InvalidLength("A".to_string())));
+    assert_eq!("As Kd As".parse::<PokerDeck>().err(), Some(ParseCardError::DuplicateCard("As".to_string())));
+    assert!("As Ad".parse::<PokerDeck>().is_ok());
+}
@@ -0,0 +1,84 @@
+use std::rc::Rc;
+use poker_core::entity::card::{Card, PokerCard, PokerDeck, Type};
+use poker_core::entity::card_set::CardSet;
+use poker_core::error::PokerError;
+use poker_core::service::mau_binh_service::MauBinhArrangement;
+
+mod common;
+use common::cards;
+
+fn set(notation : &str) -> CardSet {
+    CardSet::new_by_cards(&cards(notation)).unwrap()
+}
+
+#[test]
+fn card_identity_includes_the_suit() {
+    let ace_of_spades = Card::new(Type::SPADE, 1).unwrap();
+    let ace_of_hearts = Card::new(Type::HEART, 14).unwrap();
+    assert_ne!(ace_of_spades, ace_of_hearts);
+    assert_eq!(ace_of_spades, Card::new(Type::SPADE, 14).unwrap());
+    assert_eq!(Card::new(Type::SPADE, 2).unwrap().get_index(), 0);
+    assert_eq!(Card::new(Type::DIAMOND, 1).unwrap().get_index(), 51);
+    assert_eq!(Card::new(Type::CLUB, 15), None);
+    assert_eq!(Card::new_by_index(52), None);
+    //PokerCard only compares the number, the set still tells the two aces apart
+    assert!(PokerCard::new_by_attribute(Type::SPADE, 1) == PokerCard::new_by_attribute(Type::HEART, 1));
+    assert_eq!(set("As Ah").len(), 2);
+}
+
+#[test]
+fn set_operations_follow_the_bits() {
+    let hand = set("As Ah Kd");
+    let board = set("Ah Qc Kd 2s");
+    assert_eq!(hand.union(&board), set("As Ah Kd Qc 2s"));
+    assert_eq!(hand.intersection(&board), set("Ah Kd"));
+    assert_eq!(hand.difference(&board), set("As"));
+    assert!(!hand.is_disjoint(&board));
+    assert!(hand.is_disjoint(&set("Qc 2s")));
+    assert_eq!(CardSet::new_full().len(), 52);
+    assert!(CardSet::new().is_empty());
+    assert_eq!(CardSet::new_full().difference(&board).len(), 48);
+}
+
+#[test]
+fn insert_and_remove_report_changes() {
+    let ace = Card::new(Type::CLUB, 1).unwrap();
+    let mut cards = CardSet::new();
+    assert!(cards.insert(ace));
+    assert!(!cards.insert(ace));
+    assert!(cards.contains(ace));
+    assert!(cards.remove(ace));
+    assert!(!cards.remove(ace));
+    assert!(cards.is_empty());
+}
+
+#[test]
+fn iteration_goes_from_the_lowest_index() {
+    let cards = set("Ad 2s Kh 2c");
+    let listed = cards.iter().map(|card| card.to_string()).collect::<Vec<_>>();
+    assert_eq!(listed, vec!["2s", "2c", "Kh", "Ad"]);
+    assert_eq!(cards.iter().collect::<CardSet>(), cards);
+}
+
+#[test]
+fn duplicates_and_jokers_are_rejected() {
+    let mut duplicate = cards("As Kd");
+    duplicate.push(Rc::new(PokerCard::new_by_attribute(Type::SPADE, 1)));
+    assert_eq!(CardSet::new_by_cards(&duplicate), Err(PokerError::DuplicateCard(String::from("As"))));
+    assert_eq!(CardSet::new_by_cards(&cards("As Xh")), Err(PokerError::InvalidCard(String::from("Xh"))));
+
+    assert!("As Kd As".parse::<PokerDeck>().is_err());
+    assert_eq!("As Ah Ad".parse::<PokerDeck>().unwrap().get_cards().len(), 3);
+    assert_eq!("Xs Xs".parse::<PokerDeck>().unwrap().get_cards().len(), 2);
+}
+
+#[test]
+fn mau_binh_arrangement_rejects_the_same_card_twice() {
+    let arrangement = MauBinhArrangement::new(&cards("2s 4d 7c"), &cards("3h 3c 9c Jh Qs"), &cards("Th 9d 6c 6h 6d"));
+    assert!(arrangement.is_ok());
+    let arrangement = MauBinhArrangement::new(&cards("2s 4d 7c"), &cards("3h 3c 9c Jh Qs"), &cards("Th 9d 6c 6h 2s"));
+    assert_eq!(arrangement.err(), Some(PokerError::DuplicateCard(String::from("2s"))));
+    //same numbers in other suits are different cards
+    let arrangement = MauBinhArrangement::new(&cards("2s 4d 7c"), &cards("3h 3c 9c Jh Qs"), &cards("Th 9d 6c 6h 2h"));
+    assert!(arrangement.is_ok());
+}
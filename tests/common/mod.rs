@@ -0,0 +1,19 @@
+#![allow(dead_code)]
+
+use std::rc::Rc;
+use poker_core::entity::card::{Card, PokerCard, PokerDeck, SolitaireCard};
+use poker_core::entity::deck::DeckCard;
+
+//every test binary builds its own copy, and most only use one of the helpers
+
+pub fn cards(notation : &str) -> Vec<Rc<PokerCard>> {
+    notation.parse::<PokerDeck>().unwrap().into_cards().into_iter().map(Rc::new).collect()
+}
+
+pub fn solitaire_cards(notation : &str) -> Vec<SolitaireCard> {
+    notation.parse::<PokerDeck>().unwrap().into_cards().iter().map(|card| card.get_solitaire_card()).collect()
+}
+
+pub fn identities(notation : &str) -> Vec<Card> {
+    notation.parse::<PokerDeck>().unwrap().into_cards().iter().map(|card| card.get_card().unwrap()).collect()
+}
\ No newline at end of file
@@ -0,0 +1,127 @@
+use std::collections::HashSet;
+use poker_core::entity::card::{PokerCard, SolitaireCard, Type};
+use poker_core::entity::deck::{Deck, DeckSpec};
+use poker_core::entity::ruleset::Ruleset;
+use poker_core::error::PokerError;
+use poker_core::service::blackjack_service::{BlackjackAction, BlackjackPhase, BlackjackTable, BlackjackVariant};
+
+#[test]
+fn specs_size_multi_deck_and_short_deck() {
+    assert_eq!(DeckSpec::new_with_decks(6).get_size(), 312);
+    assert_eq!(DeckSpec::new_by_ruleset(Ruleset::ShortDeck { trips_beat_straight : false }).get_size(), 36);
+    assert_eq!(DeckSpec::new(2, &[1, 10, 11, 12, 13]).unwrap().get_size(), 40);
+    assert_eq!(DeckSpec::new(1, &[1, 1]), Err(PokerError::InvalidDeck));
+    assert_eq!(DeckSpec::new(1, &[0, 5]), Err(PokerError::InvalidDeck));
+    assert_eq!(DeckSpec::new(1, &[14]), Err(PokerError::InvalidDeck));
+
+    let shoe : Deck<SolitaireCard> = Deck::new_with_seed(DeckSpec::new_with_decks(6), 1);
+    assert_eq!(shoe.get_remaining(), 312);
+    let aces_of_spades = shoe.get_cards().iter().filter(|card| **card == SolitaireCard::new(Type::SPADE, 1)).count();
+    assert_eq!(aces_of_spades, 6);
+}
+
+#[test]
+fn peek_shows_the_next_cards_in_dealing_order() {
+    let mut deck : Deck<PokerCard> = Deck::new_with_seed(DeckSpec::default(), 7);
+    let next = deck.peek(3).into_iter().map(|card| card.get_copy()).collect::<Vec<_>>();
+    assert_eq!(deck.get_remaining(), 52);
+    let drawn = deck.draw(3).unwrap();
+    assert!(next.iter().zip(drawn.iter()).all(|(peeked, drawn)| peeked.is_same_card(drawn)));
+    assert_eq!(deck.get_remaining(), 49);
+}
+
+#[test]
+fn burned_cards_go_to_the_muck() {
+    let mut deck : Deck<PokerCard> = Deck::new_with_seed(DeckSpec::default(), 3);
+    let burned = deck.peek(1)[0].get_copy();
+    let after = deck.peek(2)[1].get_copy();
+    deck.burn().unwrap();
+    assert_eq!(deck.get_remaining(), 51);
+    assert_eq!(deck.get_muck().len(), 1);
+    assert!(deck.get_muck()[0].is_same_card(&burned));
+    assert!(deck.draw_one().unwrap().is_same_card(&after));
+}
+
+#[test]
+fn muck_is_shuffled_back_when_the_stub_runs_out() {
+    let mut deck : Deck<PokerCard> = Deck::new_with_seed(DeckSpec::default(), 11);
+    let dealt = deck.draw(50).unwrap();
+    deck.discard(&dealt.iter().take(10).collect::<Vec<_>>());
+    assert_eq!(deck.draw(13), Err(PokerError::DeckExhausted));
+    let drawn = deck.draw(12).unwrap();
+    assert_eq!(deck.get_remaining(), 0);
+    assert!(deck.get_muck().is_empty());
+    //the last two stub cards come first, the rest are the discards
+    assert!(drawn[2..].iter().all(|card| dealt[..10].iter().any(|other| other.is_same_card(card))));
+
+    deck.shuffle();
+    assert_eq!(deck.get_remaining(), 52);
+    assert!(deck.get_muck().is_empty());
+}
+
+#[test]
+fn remove_cards_takes_one_copy_out_of_the_stub() {
+    let ace = PokerCard::new_by_attribute(Type::HEART, 1);
+    let mut shoe : Deck<PokerCard> = Deck::new_with_seed(DeckSpec::new_with_decks(2), 5);
+    shoe.remove_cards(&[&ace]).unwrap();
+    assert_eq!(shoe.get_remaining(), 103);
+    assert_eq!(shoe.get_cards().iter().filter(|card| card.is_same_card(&ace)).count(), 1);
+    shoe.remove_cards(&[&ace]).unwrap();
+    assert_eq!(shoe.remove_cards(&[&ace]), Err(PokerError::CardNotInDeck(ace.to_string())));
+
+    let mut short : Deck<PokerCard> = Deck::new_with_seed(DeckSpec::new_by_ruleset(Ruleset::ShortDeck { trips_beat_straight : false }), 5);
+    let deuce = PokerCard::new_by_attribute(Type::CLUB, 2);
+    assert_eq!(short.remove_cards(&[&deuce]), Err(PokerError::CardNotInDeck(deuce.to_string())));
+}
+
+#[test]
+fn cut_card_marks_the_reshuffle_point() {
+    let mut shoe : Deck<SolitaireCard> = Deck::new_shoe_with_seed(DeckSpec::new_with_decks(2), 30, 9);
+    assert_eq!(shoe.get_cut_card(), 30);
+    shoe.draw(73).unwrap();
+    assert!(!shoe.is_cut_card_reached());
+    shoe.draw_one().unwrap();
+    assert_eq!(shoe.get_remaining(), 30);
+    assert!(shoe.is_cut_card_reached());
+    shoe.shuffle();
+    assert!(!shoe.is_cut_card_reached());
+    assert_eq!(shoe.get_remaining(), 104);
+}
+
+#[test]
+fn jokers_only_for_cards_that_hold_them() {
+    let deck : Deck<PokerCard> = Deck::new_with_jokers(DeckSpec::default(), 2, 1).unwrap();
+    assert_eq!(deck.get_remaining(), 54);
+    assert_eq!(deck.get_jokers(), 2);
+    let jokers = deck.get_cards().iter().filter(|card| card.is_joker()).map(|card| card.get_card_type()).collect::<HashSet<_>>();
+    assert_eq!(jokers.len(), 2);
+}
+
+#[test]
+fn same_seed_replays_the_same_order() {
+    let first : Deck<PokerCard> = Deck::new_with_seed(DeckSpec::new_with_decks(2), 42);
+    let second : Deck<PokerCard> = Deck::new_with_seed(DeckSpec::new_with_decks(2), 42);
+    let other : Deck<PokerCard> = Deck::new_with_seed(DeckSpec::new_with_decks(2), 43);
+    assert_eq!(first.get_seed(), Some(42));
+    assert!(first.get_cards().iter().zip(second.get_cards().iter()).all(|(a, b)| a.is_same_card(b)));
+    assert!(!first.get_cards().iter().zip(other.get_cards().iter()).all(|(a, b)| a.is_same_card(b)));
+}
+
+#[test]
+fn blackjack_reshuffles_the_shoe_once_the_cut_card_is_reached() {
+    let shoe = Deck::new_shoe_with_seed(DeckSpec::new_with_decks(2), 100, 9);
+    let mut table = BlackjackTable::new(BlackjackVariant::Blackjack { dealer_hits_soft_17 : false }, shoe);
+    let mut rounds = 0;
+    while !table.get_shoe().is_cut_card_reached() {
+        table.start_round(&[10]).unwrap();
+        while let Some(hand) = table.get_to_act() {
+            let action = if table.get_phase() == BlackjackPhase::Insurance { BlackjackAction::DeclineInsurance } else { BlackjackAction::Stand };
+            table.act(hand, action).unwrap();
+        }
+        rounds += 1;
+    }
+    assert_eq!(rounds, 1);
+    table.start_round(&[10]).unwrap();
+    let dealt = table.get_hands()[0].get_cards().len() + table.get_dealer_cards().len();
+    assert_eq!(table.get_shoe().get_remaining(), 104 - dealt);
+}
@@ -0,0 +1,63 @@
+use poker_core::entity::hand::HandCategory;
+use poker_core::entity::ruleset::{Ruleset, WildCards};
+use poker_core::service::poker_service::{hand_value_by_one_deck, score_by_one_deck, score_by_one_deck_with_wild_cards, score_partial};
+use poker_core::util::description_util::{describe_chosen, describe_hand_value, English, HandDescription, HandLocale, Vietnamese};
+
+mod common;
+use common::cards;
+
+//the hand value and the chosen cards must read the same
+fn describe(notation : &str, locale : &impl HandLocale) -> String {
+    let deck = cards(notation);
+    let by_value = describe_hand_value(&hand_value_by_one_deck(&deck).unwrap(), locale);
+    let (_, chosen, category) = score_by_one_deck(&deck).unwrap();
+    assert_eq!(describe_chosen(category, &chosen, locale), by_value);
+    by_value
+}
+
+#[test]
+fn english_names_the_hand_and_its_kickers() {
+    assert_eq!(describe("Ks Kd 7c 7h Qs 2d", &English), "Two Pair, Kings and Sevens with a Queen kicker");
+    assert_eq!(describe("As 2d 3c 4h 5s 9d", &English), "Straight, Five high");
+    assert_eq!(describe("As Ks Qs Js Ts", &English), "Royal Flush");
+    assert_eq!(describe("9h 8h 7h 6h 5h", &English), "Straight Flush, Nine high");
+    assert_eq!(describe("6s 6d 6c Kh Ks", &English), "Full House, Sixes full of Kings");
+    assert_eq!(describe("8s 8d 8c 8h As", &English), "Four of a Kind, Eights with an Ace kicker");
+    assert_eq!(describe("Qs Qd Qc 9h 4s", &English), "Three of a Kind, Queens with Nine and Four kickers");
+    assert_eq!(describe("Kh Jh 8h 4h 2h", &English), "Flush, King high");
+    assert_eq!(describe("As Ad 9c 7h 2s", &English), "Pair of Aces with Nine, Seven and Two kickers");
+    assert_eq!(describe("Ks Jd 9c 7h 2s", &English), "High Card, King with Jack, Nine, Seven and Two kickers");
+}
+
+#[test]
+fn vietnamese_uses_the_table_names() {
+    assert_eq!(describe("Ks Kd 7c 7h Qs", &Vietnamese), "Thú, đôi Già và đôi Bảy, kèm Đầm");
+    assert_eq!(describe("As 2d 3c 4h 5s", &Vietnamese), "Sảnh, lá cao nhất Năm");
+    assert_eq!(describe("As Ks Qs Js Ts", &Vietnamese), "Thùng phá sảnh lớn");
+    assert_eq!(describe("6s 6d 6c Kh Ks", &Vietnamese), "Cù lũ, ba lá Sáu và đôi Già");
+    assert_eq!(describe("As Ad 9c 7h 2s", &Vietnamese), "Đôi Át, kèm Chín, Bảy, Hai");
+    assert_eq!(Vietnamese.category_name(HandCategory::FourOfAKind), "Tứ quý");
+}
+
+#[test]
+fn partial_and_wild_hands_are_described_from_their_chosen_cards() {
+    let (_, chosen, category) = score_partial(&cards("Kd Ks")).unwrap();
+    assert_eq!(describe_chosen(category, &chosen, &English), "Pair of Kings");
+    let (_, chosen, category) = score_partial(&cards("Kd 7s 2c")).unwrap();
+    assert_eq!(describe_chosen(category, &chosen, &English), "High Card, King with Seven and Two kickers");
+
+    let (_, chosen, category) = score_by_one_deck_with_wild_cards(&cards("Xs Kh Kd Kc Ks"), &WildCards::new(&[], false), Ruleset::Standard).unwrap();
+    assert_eq!(describe_chosen(category, &chosen, &English), "Five of a Kind, Kings");
+    assert_eq!(describe_chosen(category, &chosen, &Vietnamese), "Ngũ quý Già");
+}
+
+#[test]
+fn description_splits_main_numbers_from_kickers() {
+    let description = HandDescription::new(HandCategory::TwoPair, &[13, 7, 12]);
+    assert_eq!(description.get_main(), &vec![13, 7]);
+    assert_eq!(description.get_kickers(), &vec![12]);
+    let description = HandDescription::new(HandCategory::Straight, &[5, 4, 3, 2, 14]);
+    assert_eq!(description.get_main(), &vec![5]);
+    assert!(description.get_kickers().is_empty());
+    assert!(HandDescription::new(HandCategory::RoyalFlush, &[14, 13, 12, 11, 10]).get_main().is_empty());
+}
@@ -0,0 +1,67 @@
+use std::collections::HashSet;
+use std::rc::Rc;
+use poker_core::entity::card::PokerCard;
+use poker_core::error::{ActionError, PokerError};
+use poker_core::service::draw_service::{DrawGame, DrawStreet, DrawTable};
+use poker_core::service::holdem_service::Action;
+use poker_core::service::poker_service::Match;
+
+fn names(cards : &[Rc<PokerCard>]) -> Vec<String> {
+    cards.iter().map(|card| card.to_string()).collect()
+}
+
+//checks or calls every betting round and hands the whole hand back at every draw
+fn play_out(table : &mut DrawTable, mut on_draw : impl FnMut(&DrawTable, usize, &[Rc<PokerCard>])) {
+    while table.is_hand_in_progress() {
+        let seat = table.get_to_act().unwrap();
+        match table.get_street() {
+            DrawStreet::Betting => {
+                let action = if table.get_call_amount(seat) > 0 { Action::Call } else { Action::Check };
+                table.act(seat, action).unwrap();
+            }
+            _ => {
+                let hand = table.get_seats()[seat].get_hand().clone();
+                table.discard(seat, &hand).unwrap();
+                on_draw(table, seat, &hand);
+            }
+        }
+    }
+}
+
+#[test]
+fn triple_draw_reshuffles_the_muck_without_returning_own_discards() {
+    let mut table = DrawTable::new(DrawGame::DeuceToSevenTripleDraw, vec![1000; 6], 5, 10);
+    table.start_hand(Match::new_with_seed(11)).unwrap();
+    let mut draws = 0;
+    play_out(&mut table, |table, seat, discarded| {
+        draws += 1;
+        let hand = names(table.get_seats()[seat].get_hand());
+        assert_eq!(hand.len(), 5);
+        assert!(names(discarded).iter().all(|card| !hand.contains(card)), "seat {} got its own discards back", seat);
+        let live = table.get_seats().iter().flat_map(|seat| names(seat.get_hand())).collect::<Vec<_>>();
+        assert_eq!(live.iter().collect::<HashSet<_>>().len(), 30, "a card is in two hands");
+    });
+    //six players drawing five cards three times need 90 cards from a 22-card stub
+    assert_eq!(draws, 18);
+    let settlement = table.get_settlement().unwrap();
+    assert!(settlement.is_shown_down());
+    assert_eq!(table.get_seats().iter().map(|seat| seat.get_stack()).sum::<u64>(), 6000);
+}
+
+#[test]
+fn betting_is_closed_while_drawing() {
+    let mut table = DrawTable::new(DrawGame::FiveCardDraw, vec![1000; 3], 5, 10);
+    table.start_hand(Match::new_with_seed(5)).unwrap();
+    for _ in 0..3 {
+        let seat = table.get_to_act().unwrap();
+        let action = if table.get_call_amount(seat) > 0 { Action::Call } else { Action::Check };
+        table.act(seat, action).unwrap();
+    }
+    assert_eq!(table.get_street(), DrawStreet::Drawing);
+    let seat = table.get_to_act().unwrap();
+    assert_eq!(table.act(seat, Action::Check), Err(PokerError::IllegalAction(ActionError::DrawInProgress)));
+    let other = table.get_seats()[(seat + 1) % 3].get_hand()[0].clone();
+    assert_eq!(table.discard(seat, &[other]), Err(PokerError::IllegalAction(ActionError::InvalidDiscard)));
+    table.discard::<Rc<PokerCard>>(seat, &[]).unwrap();
+    assert_eq!(table.get_draws_done(), 0);
+}
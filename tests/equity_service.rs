@@ -0,0 +1,94 @@
+use std::rc::Rc;
+use poker_core::entity::card::PokerCard;
+use poker_core::error::PokerError;
+use poker_core::service::equity_service::{calculate_equity, calculate_equity_parallel, EquityMode, EquityResult};
+
+mod common;
+use common::{cards, identities};
+
+fn counts(result : &EquityResult) -> Vec<(u64, u64, u64)> {
+    result.get_players().iter().map(|player| (player.get_wins(), player.get_ties(), player.get_losses())).collect()
+}
+
+//same-suited aces against kings run at 82.64% over every board
+#[test]
+fn aces_beat_kings_about_82_percent_preflop() {
+    let mode = EquityMode::MonteCarlo { iterations : 20_000, seed : 82 };
+    let result = calculate_equity(&[cards("As Ah"), cards("Ks Kh")], &[], &[], &mode).unwrap();
+    assert_eq!(result.get_boards(), 20_000);
+    let aces = result.get_players()[0].get_equity_percent();
+    let kings = result.get_players()[1].get_equity_percent();
+    assert!((aces - 82.6).abs() < 1.0, "aces have {}%", aces);
+    assert!((aces + kings - 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn exhaustive_equity_counts_every_river() {
+    let mode = EquityMode::Exhaustive;
+    let result = calculate_equity(&[cards("As Ad"), cards("Kh Kc")], &cards("Ks 7d 2c 9h"), &[], &mode).unwrap();
+    assert_eq!(result.get_boards(), 44);
+    let kings = &result.get_players()[1];
+    assert_eq!((kings.get_wins(), kings.get_ties(), kings.get_losses()), (42, 0, 2));
+}
+
+#[test]
+fn monte_carlo_replays_for_the_same_seed() {
+    let run = |seed : u64| {
+        let mode = EquityMode::MonteCarlo { iterations : 2_000, seed };
+        calculate_equity(&[cards("Js Ts"), cards("Ah Kd")], &[], &[], &mode).unwrap().get_players()[0].get_wins()
+    };
+    assert_eq!(run(9), run(9));
+}
+
+#[test]
+fn equity_fails_when_the_board_cannot_be_dealt() {
+    let hands = PokerCard::get_all_card().chunks(2).take(24)
+        .map(|pair| pair.iter().map(|card| Rc::new(card.get_copy())).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    for mode in [EquityMode::Exhaustive, EquityMode::MonteCarlo { iterations : 10, seed : 1 }] {
+        assert!(matches!(calculate_equity(&hands, &[], &[], &mode), Err(PokerError::DeckExhausted)));
+    }
+    assert!(matches!(calculate_equity(&[cards("As Ad")], &[], &[], &EquityMode::Exhaustive), Err(PokerError::TooFewPlayers { .. })));
+    assert!(calculate_equity(&[cards("As Ad"), cards("As Kc")], &[], &[], &EquityMode::Exhaustive).is_err());
+}
+
+#[test]
+fn parallel_exhaustive_matches_sequential_for_any_worker_count() {
+    for (hands, board, boards) in [(["As Ad", "Kh Kc", "8s 7s"], "Ks 7d 2c 9h", 42), (["Ah Kh", "Qs Qd", "Tc 9c"], "Qh 8c 3h", 903)] {
+        let sequential = calculate_equity(&hands.map(cards), &cards(board), &[], &EquityMode::Exhaustive).unwrap();
+        assert_eq!(sequential.get_boards(), boards);
+        for workers in [1, 2, 3, 7] {
+            let parallel = calculate_equity_parallel(&hands.map(identities), &identities(board), &[], &EquityMode::Exhaustive, workers).unwrap();
+            assert_eq!(parallel.get_boards(), boards);
+            assert_eq!(counts(&parallel), counts(&sequential), "{} workers", workers);
+        }
+    }
+}
+
+#[test]
+fn parallel_monte_carlo_replays_for_the_same_seed_and_workers() {
+    let hands = [identities("As Ah"), identities("Ks Kh")];
+    let run = |workers : usize| {
+        let mode = EquityMode::MonteCarlo { iterations : 20_000, seed : 5 };
+        calculate_equity_parallel(&hands, &[], &[], &mode, workers).unwrap()
+    };
+    let result = run(4);
+    assert_eq!(counts(&result), counts(&run(4)));
+    assert_eq!(run(3).get_boards(), 20_000);
+    let aces = result.get_players()[0].get_equity_percent();
+    assert!((aces - 82.6).abs() < 1.0, "aces have {}%", aces);
+}
+
+#[test]
+fn parallel_equity_rejects_bad_input() {
+    let mode = EquityMode::Exhaustive;
+    assert_eq!(calculate_equity_parallel(&[identities("As Ad"), identities("As Kc")], &[], &[], &mode, 2).unwrap_err(), PokerError::DuplicateCard(String::from("As")));
+    assert_eq!(calculate_equity_parallel(&[identities("As Ad"), identities("Ks Kc")], &identities("Ad 7c 2h"), &[], &mode, 2).unwrap_err(), PokerError::DuplicateCard(String::from("Ad")));
+    assert!(matches!(calculate_equity_parallel(&[identities("As Ad")], &[], &[], &mode, 2), Err(PokerError::TooFewPlayers { .. })));
+    assert!(matches!(calculate_equity_parallel(&[identities("As Ad Ah"), identities("Ks Kc")], &[], &[], &mode, 2), Err(PokerError::InvalidHandSize { .. })));
+    assert!(matches!(calculate_equity_parallel(&[identities("As Ad"), identities("Ks Kc")], &identities("2c 3c 4c 5c 6c 7c"), &[], &mode, 2), Err(PokerError::TooManyCards { .. })));
+    let hands = PokerCard::get_all_card().chunks(2).take(24)
+        .map(|pair| pair.iter().map(|card| card.get_card().unwrap()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(calculate_equity_parallel(&hands, &[], &[], &mode, 2).unwrap_err(), PokerError::DeckExhausted);
+}
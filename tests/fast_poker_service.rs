@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use poker_core::entity::card::{Card, PokerCard, PokerDeck, Type};
+use poker_core::error::PokerError;
+use poker_core::service::fast_poker_service::{fast_score, fast_score_by_cards, strength_category};
+use poker_core::service::poker_service::{compare_score, score_by_one_deck};
+
+fn all_cards() -> Vec<Rc<PokerCard>> {
+    PokerCard::get_all_card().into_iter().map(Rc::new).collect()
+}
+
+//one hand per equivalence class: every five distinct numbers once suited and once offsuit, and every
+//way to repeat numbers (the i-th copy of a number takes the i-th suit); fast_score only looks at the
+//numbers and whether the hand is suited, so agreeing on these is agreeing on every five-card hand
+fn class_representatives() -> Vec<Vec<Rc<PokerCard>>> {
+    let suits = [Type::SPADE, Type::HEART, Type::CLUB, Type::DIAMOND];
+    let card = |card_type : Type, number : i16| Rc::new(PokerCard::new_by_attribute(card_type, if number == 14 { 1 } else { number }));
+    let mut hands = Vec::new();
+    let mut numbers = Vec::new();
+    collect_numbers(2, &mut numbers, &mut |numbers| {
+        let mut copies = HashMap::new();
+        let distinct = numbers.windows(2).all(|pair| pair[0] != pair[1]);
+        let offsuit = numbers.iter().enumerate()
+            .map(|(i, &number)| {
+                let copy = copies.entry(number).or_insert(0);
+                *copy += 1;
+                card(if distinct { suits[(i == 4) as usize] } else { suits[*copy - 1] }, number)
+            })
+            .collect::<Vec<_>>();
+        hands.push(offsuit);
+        if distinct {
+            hands.push(numbers.iter().map(|&number| card(Type::SPADE, number)).collect());
+        }
+    });
+    hands
+}
+
+//non-decreasing five numbers from 2 to 14, at most four of each
+fn collect_numbers(from : i16, numbers : &mut Vec<i16>, action : &mut impl FnMut(&[i16])) {
+    if numbers.len() == 5 {
+        action(numbers);
+        return;
+    }
+    for number in from..=14 {
+        if numbers.iter().filter(|&&other| other == number).count() == 4 {
+            continue;
+        }
+        numbers.push(number);
+        collect_numbers(number, numbers, action);
+        numbers.pop();
+    }
+}
+
+#[test]
+fn fast_score_agrees_with_compare_score_on_every_hand_class() {
+    let hands = class_representatives();
+    assert_eq!(hands.len(), 7462);
+    let mut score_by_strength = HashMap::new();
+    for hand in hands.iter() {
+        let (score, _, category) = score_by_one_deck(hand).unwrap();
+        let strength = fast_score(hand).unwrap();
+        assert_eq!(strength_category(strength), category, "{:?}", hand);
+        assert!(score_by_strength.insert(strength, score).is_none(), "strength {} maps to two classes", strength);
+    }
+    let mut strengths = score_by_strength.keys().copied().collect::<Vec<_>>();
+    strengths.sort();
+    assert_eq!(strengths, (1..=7462).collect::<Vec<_>>());
+    for pair in strengths.windows(2) {
+        assert!(compare_score(&score_by_strength[&pair[1]], &score_by_strength[&pair[0]]).is_gt());
+    }
+}
+
+#[test]
+fn fast_score_agrees_with_compare_score_on_seven_card_sample() {
+    let mut cards = all_cards();
+    let mut rng = ChaCha8Rng::seed_from_u64(7);
+    for _ in 0..10_000 {
+        cards.shuffle(&mut rng);
+        let hand_1 = &cards[0..7];
+        let hand_2 = &cards[7..14];
+        let (score_1, _, _) = score_by_one_deck(hand_1).unwrap();
+        let (score_2, _, _) = score_by_one_deck(hand_2).unwrap();
+        let strength_1 = fast_score(hand_1).unwrap();
+        let strength_2 = fast_score(hand_2).unwrap();
+        assert_eq!(strength_1.cmp(&strength_2), compare_score(&score_1, &score_2), "{:?} vs {:?}", hand_1, hand_2);
+        let six = &cards[14..20];
+        let (score_6, _, _) = score_by_one_deck(six).unwrap();
+        assert_eq!(strength_1.cmp(&fast_score(six).unwrap()), compare_score(&score_1, &score_6), "{:?} vs {:?}", hand_1, six);
+    }
+}
+
+#[test]
+fn fast_score_agrees_with_compare_score_on_five_card_sample() {
+    let mut cards = all_cards();
+    let mut rng = ChaCha8Rng::seed_from_u64(5);
+    for _ in 0..20_000 {
+        cards.shuffle(&mut rng);
+        let (hand_1, hand_2) = (&cards[0..5], &cards[5..10]);
+        let (score_1, _, category) = score_by_one_deck(hand_1).unwrap();
+        let (score_2, _, _) = score_by_one_deck(hand_2).unwrap();
+        let strength_1 = fast_score(hand_1).unwrap();
+        assert_eq!(strength_category(strength_1), category);
+        assert_eq!(strength_1.cmp(&fast_score(hand_2).unwrap()), compare_score(&score_1, &score_2), "{:?} vs {:?}", hand_1, hand_2);
+    }
+}
+
+#[test]
+fn fast_score_rejects_duplicate_and_invalid_cards() {
+    let hand = |notation : &str| notation.split(' ').map(|card| {
+        let mut cards = card.parse::<PokerDeck>().unwrap().into_cards();
+        Rc::new(cards.remove(0))
+    }).collect::<Vec<_>>();
+    assert!(matches!(fast_score(&hand("As Ah Ad Ac Kd As")), Err(PokerError::DuplicateCard(_))));
+    assert!(matches!(fast_score(&hand("As Ks Qs Js Ts As")), Err(PokerError::DuplicateCard(_))));
+    assert!(matches!(fast_score(&hand("As As Kd Qc 9h")), Err(PokerError::DuplicateCard(_))));
+    assert!(fast_score(&hand("As Kd")).is_err());
+    let mut joker = hand("As Kd Qc 9h");
+    joker.push(Rc::new(PokerCard::new_joker(Type::SPADE)));
+    assert!(matches!(fast_score(&joker), Err(PokerError::InvalidCard(_))));
+    let mut out_of_range = hand("As Kd Qc 9h");
+    out_of_range.push(Rc::new(PokerCard::new_by_attribute(Type::HEART, 14)));
+    assert!(matches!(fast_score(&out_of_range), Err(PokerError::InvalidCard(_))));
+}
+
+#[test]
+fn fast_score_by_cards_rejects_duplicates_and_wrong_sizes() {
+    let ace = Card::new(Type::SPADE, 1).unwrap();
+    let others = [Card::new(Type::HEART, 13).unwrap(), Card::new(Type::CLUB, 9).unwrap(), Card::new(Type::DIAMOND, 5).unwrap()];
+    let hand = [ace, others[0], others[1], others[2], ace];
+    assert_eq!(fast_score_by_cards(&hand), Err(PokerError::DuplicateCard(String::from("As"))));
+    assert!(matches!(fast_score_by_cards(&hand[..4]), Err(PokerError::InvalidHandSize { .. })));
+    let mut valid = hand[..4].to_vec();
+    valid.push(Card::new(Type::SPADE, 2).unwrap());
+    assert_eq!(fast_score_by_cards(&valid), fast_score(&valid.iter().map(|card| Rc::new(card.to_poker_card())).collect::<Vec<_>>()));
+}
@@ -0,0 +1,55 @@
+use poker_core::error::{ActionError, PokerError};
+use poker_core::service::holdem_service::{Action, HoldemTable, Street};
+use poker_core::service::poker_service::Match;
+
+//button on seat 0, blinds on seats 1 and 2, seat 0 is first to act
+fn start(stacks : Vec<u64>) -> HoldemTable {
+    let mut table = HoldemTable::new(stacks, 5, 10);
+    table.start_hand(Match::new_with_seed(3)).unwrap();
+    assert_eq!(table.get_button(), Some(0));
+    assert_eq!(table.get_to_act(), Some(0));
+    table
+}
+
+#[test]
+fn raises_must_be_at_least_the_last_full_raise() {
+    let mut table = start(vec![1000, 1000, 1000]);
+    assert_eq!(table.get_min_raise_to(), 20);
+    assert_eq!(table.act(0, Action::Raise(15)), Err(PokerError::IllegalAction(ActionError::RaiseTooSmall { minimum : 20 })));
+    assert_eq!(table.act(0, Action::Bet(40)), Err(PokerError::IllegalAction(ActionError::CannotBet)));
+    table.act(0, Action::Raise(100)).unwrap();
+    assert_eq!(table.get_min_raise_to(), 190);
+    assert_eq!(table.act(1, Action::Raise(150)), Err(PokerError::IllegalAction(ActionError::RaiseTooSmall { minimum : 190 })));
+    table.act(1, Action::Raise(190)).unwrap();
+    assert_eq!(table.get_current_bet(), 190);
+    assert_eq!(table.get_call_amount(2), 180);
+    assert_eq!(table.act(0, Action::Call), Err(PokerError::IllegalAction(ActionError::NotYourTurn { expected : 2 })));
+}
+
+#[test]
+fn incomplete_all_in_does_not_reopen_betting() {
+    let mut table = start(vec![1000, 1000, 130]);
+    table.act(0, Action::Raise(100)).unwrap();
+    table.act(1, Action::Call).unwrap();
+    //the big blind shoves 30 more, short of the 90 needed for a full raise
+    table.act(2, Action::AllIn).unwrap();
+    assert_eq!(table.get_current_bet(), 130);
+    assert_eq!(table.act(0, Action::Raise(300)), Err(PokerError::IllegalAction(ActionError::CannotRaise)));
+    assert_eq!(table.act(0, Action::AllIn), Err(PokerError::IllegalAction(ActionError::CannotRaise)));
+    table.act(0, Action::Call).unwrap();
+    assert_eq!(table.act(1, Action::Raise(300)), Err(PokerError::IllegalAction(ActionError::CannotRaise)));
+    table.act(1, Action::Call).unwrap();
+    assert_eq!(table.get_street(), Street::Flop);
+    assert_eq!(table.get_pot(), 390);
+}
+
+#[test]
+fn full_all_in_raise_reopens_betting() {
+    let mut table = start(vec![1000, 1000, 300]);
+    table.act(0, Action::Raise(100)).unwrap();
+    table.act(1, Action::Call).unwrap();
+    table.act(2, Action::AllIn).unwrap();
+    assert_eq!(table.get_min_raise_to(), 500);
+    table.act(0, Action::Raise(500)).unwrap();
+    assert_eq!(table.get_current_bet(), 500);
+}
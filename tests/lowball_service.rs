@@ -0,0 +1,61 @@
+use poker_core::error::PokerError;
+use poker_core::service::lowball_service::{ace_to_five_score, badugi_score, compare_lowball_score, deuce_to_seven_score, eight_or_better_score};
+
+mod common;
+use common::cards;
+
+#[test]
+fn ace_to_five_ignores_straights_and_flushes() {
+    let (wheel, _) = ace_to_five_score(&cards("As 2s 3s 4s 5s")).unwrap();
+    assert_eq!(wheel, [0, 5, 4, 3, 2, 1]);
+    let (seven, chosen) = ace_to_five_score(&cards("Ah 2d 3c 4h 4s 7d Kc")).unwrap();
+    assert_eq!(seven, [0, 7, 4, 3, 2, 1]);
+    assert!(chosen.iter().all(|card| card.get_number() != 13));
+    let (paired, _) = ace_to_five_score(&cards("Ah Ad 3c 4h 5s")).unwrap();
+    assert!(compare_lowball_score(&wheel, &seven).is_gt());
+    assert!(compare_lowball_score(&seven, &paired).is_gt());
+}
+
+#[test]
+fn deuce_to_seven_counts_aces_high_and_straights_against_the_hand() {
+    let (best, _) = deuce_to_seven_score(&cards("7s 5d 4c 3h 2s")).unwrap();
+    let (eight, _) = deuce_to_seven_score(&cards("8s 5d 4c 3h 2s")).unwrap();
+    let (ace_high, _) = deuce_to_seven_score(&cards("As 2d 3c 4h 5s")).unwrap();
+    let (straight, _) = deuce_to_seven_score(&cards("6s 5d 4c 3h 2s")).unwrap();
+    let (flush, _) = deuce_to_seven_score(&cards("7s 5s 4s 3s 2s")).unwrap();
+    let ordered = [best, eight, ace_high, straight, flush];
+    for pair in ordered.windows(2) {
+        assert!(compare_lowball_score(&pair[0], &pair[1]).is_gt(), "{:?} should beat {:?}", pair[0], pair[1]);
+    }
+    assert_eq!(straight[0], 4);
+    assert_eq!(flush[0], 5);
+}
+
+#[test]
+fn eight_or_better_needs_five_different_low_numbers() {
+    let (low, _) = eight_or_better_score(&cards("As 2d 3c 8h 7s 9d Kc")).unwrap();
+    assert_eq!(low, [8, 7, 3, 2, 1]);
+    assert!(eight_or_better_score(&cards("As 2d 3c 8h 8s 9d Kc")).is_none());
+}
+
+#[test]
+fn badugi_prefers_more_cards_then_lower_numbers() {
+    let (best, chosen) = badugi_score(&cards("As 2d 3c 4h")).unwrap();
+    assert_eq!(best, [0, 4, 3, 2, 1, 0]);
+    assert_eq!(chosen.len(), 4);
+    let (three_card, chosen) = badugi_score(&cards("As 2s 3c 4h")).unwrap();
+    assert_eq!(three_card, [1, 4, 3, 1, 0, 0]);
+    assert_eq!(chosen.len(), 3);
+    let (king_high, _) = badugi_score(&cards("Ks Qd Jc Th")).unwrap();
+    assert!(compare_lowball_score(&best, &king_high).is_gt());
+    assert!(compare_lowball_score(&king_high, &three_card).is_gt());
+    let (one_card, _) = badugi_score(&cards("As Ah Ad Ac")).unwrap();
+    assert_eq!(one_card[0], 3);
+}
+
+#[test]
+fn low_evaluators_reject_wrong_hand_sizes() {
+    assert!(matches!(badugi_score(&cards("As 2d 3c 4h 5s")), Err(PokerError::InvalidHandSize { .. })));
+    assert!(matches!(ace_to_five_score(&cards("As 2d 3c 4h")), Err(PokerError::InvalidHandSize { .. })));
+    assert!(matches!(deuce_to_seven_score(&cards("As 2d 3c 4h 5s 6d 7c 8h")), Err(PokerError::InvalidHandSize { .. })));
+}
@@ -0,0 +1,74 @@
+use std::cmp::Ordering;
+use poker_core::entity::hand::HandCategory;
+use poker_core::service::mau_binh_service::{compare_arrangements, solve, MauBinhArrangement, MauBinhSpecial};
+
+mod common;
+use common::cards;
+
+fn arrangement(front : &str, middle : &str, back : &str) -> MauBinhArrangement {
+    MauBinhArrangement::new(&cards(front), &cards(middle), &cards(back)).unwrap()
+}
+
+#[test]
+fn front_row_compares_pair_then_kicker() {
+    let first = arrangement("Ks 5d 5c", "9s 9d 9c 2h 3h", "Ts Td Tc Th 4s");
+    let second = arrangement("Qs 5h 5s", "Js Jd Jc 2d 3d", "Qh Qd Qc 7h 7s");
+    assert_eq!(first.get_front().get_category(), HandCategory::Pair);
+    assert_eq!(compare_arrangements(&first, &second).get_rows()[0], Ordering::Greater);
+    let trips = arrangement("6s 6d 6c", "9s 9d 9c 2h 3h", "Ts Td Tc Th 4s");
+    assert_eq!(trips.get_front().get_category(), HandCategory::ThreeOfAKind);
+    assert!(!trips.is_fouled());
+    assert_eq!(trips.get_royalties()[0], 3);
+}
+
+#[test]
+fn a_front_stronger_than_the_middle_fouls() {
+    let fouled = arrangement("As Ad Kc", "3s 3d 4c 5h 6s", "Ts Td Tc 8h 8s");
+    assert!(fouled.is_fouled());
+    let clean = arrangement("2s 4d 7c", "3h 3c 9c Jh Qs", "Th 9d 6c 6h 6d");
+    let comparison = compare_arrangements(&fouled, &clean);
+    assert_eq!(comparison.get_rows(), &[Ordering::Less; 3]);
+    assert_eq!(comparison.get_points(), -6);
+}
+
+#[test]
+fn fouled_arrangements_keep_only_the_dealt_specials() {
+    //six pairs, but set so the front pair of aces beats the middle pair of threes
+    let fouled = arrangement("Ac Ad Ks", "3s 3d 4c 5h 6s", "4d 5d 6c 7h 7s");
+    assert!(fouled.is_fouled());
+    assert_eq!(fouled.get_special(), Some(MauBinhSpecial::SixPairs));
+    let clean = arrangement("2s 4h 8c", "3h 3c 9c Jh Qs", "Th 9d 6h 6d 2d");
+    assert_eq!(compare_arrangements(&fouled, &clean).get_points(), 3);
+
+    //a dragon set with high cards in front is still a dragon
+    let dragon = arrangement("As Kd Qc", "2s 3d 4c 5h 7s", "6d 8c 9h Ts Jd");
+    assert!(dragon.is_fouled());
+    assert_eq!(dragon.get_special(), Some(MauBinhSpecial::Dragon));
+    assert_eq!(compare_arrangements(&clean, &dragon).get_points(), -24);
+
+    //three flushes are only made by the rows, a back weaker than the middle loses them
+    let flushes = arrangement("Ah Kh Qh", "2c 4c 6c 8c 9c", "2d 3d 5d 7d 8d");
+    assert!(flushes.is_fouled());
+    assert_eq!(flushes.get_special(), None);
+    assert!(compare_arrangements(&flushes, &clean).get_points() < 0);
+    let flushes = arrangement("Ah Kh Qh", "2d 3d 5d 7d 8d", "2c 4c 6c 8c 9c");
+    assert_eq!(flushes.get_special(), Some(MauBinhSpecial::ThreeFlushes));
+
+    let solved = solve(&cards("Ac Ad Ks 3s 3d 4c 5h 6s 4d 5d 6c 7h 7s")).unwrap();
+    assert!(!solved.is_fouled());
+    assert_eq!(solved.get_special(), Some(MauBinhSpecial::SixPairs));
+    assert_eq!(compare_arrangements(&solved, &clean).get_points(), 3);
+}
+
+#[test]
+fn solver_finds_a_legal_arrangement_and_the_dragon() {
+    let solved = solve(&cards("As Kd Qc Jh 9s 9d 7c 6h 5s 4d 3c 3h 2s")).unwrap();
+    assert!(!solved.is_fouled());
+    let sizes = solved.get_rows().map(|row| row.get_cards().len());
+    assert_eq!(sizes, [3, 5, 5]);
+
+    let dragon = solve(&cards("As Kd Qc Jh Ts 9d 8c 7h 6s 5d 4c 3h 2s")).unwrap();
+    assert_eq!(dragon.get_special(), Some(MauBinhSpecial::Dragon));
+    assert_eq!(compare_arrangements(&dragon, &solved).get_points(), 24);
+    assert!(solve(&cards("As Kd Qc")).is_err());
+}
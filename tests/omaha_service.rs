@@ -0,0 +1,59 @@
+use poker_core::entity::hand::HandCategory;
+use poker_core::error::PokerError;
+use poker_core::service::omaha_service::{omaha_hi_lo_showdown, omaha_high_score, omaha_low_score, omaha_showdown};
+
+mod common;
+use common::cards;
+
+#[test]
+fn high_hand_uses_exactly_two_hole_cards() {
+    //four spades in the hole and two on the board make no flush
+    let (_, chosen, category) = omaha_high_score(&cards("As Ks Qs Js"), &cards("Ts 9s 2d 3c 4h")).unwrap();
+    assert_eq!(category, HandCategory::HighCard);
+    let hole = cards("As Ks Qs Js");
+    assert_eq!(chosen.iter().filter(|card| hole.iter().any(|own| own.to_string() == card.to_string())).count(), 2);
+    //a single heart in the hole does not complete a four-heart board
+    let (_, _, category) = omaha_high_score(&cards("Ah Kc Qd 2s"), &cards("3h 6h 9h Jh 5c")).unwrap();
+    assert_ne!(category, HandCategory::Flush);
+    let (_, _, category) = omaha_high_score(&cards("Ah Kh Qd 2s"), &cards("3h 6h 9h Jc 5c")).unwrap();
+    assert_eq!(category, HandCategory::Flush);
+}
+
+#[test]
+fn showdown_ranks_omaha_hands() {
+    let result = omaha_showdown(&[cards("As Ks Qs Js"), cards("8d 7c 2h 2c")], &cards("Ts 9s 2d 3c 4h")).unwrap();
+    assert_eq!(result.get_winners(), &vec![1]);
+    assert_eq!(result.get_players()[1].get_category(), HandCategory::ThreeOfAKind);
+}
+
+#[test]
+fn hi_lo_scoops_and_splits() {
+    let board = cards("2c 5d 7h Kc Qs");
+    let result = omaha_hi_lo_showdown(&[cards("Ac 3d Ks Kd"), cards("Ah 4s Qd Jc")], &board).unwrap();
+    assert_eq!(result.get_high_winners(), &vec![0]);
+    assert_eq!(result.get_low_winners(), &vec![0]);
+    assert!(result.is_scoop(0));
+    assert_eq!(result.split_pot(100), vec![100, 0]);
+
+    let result = omaha_hi_lo_showdown(&[cards("Ac 3d Ks Kd"), cards("Ah 3s 9d 9c")], &board).unwrap();
+    assert_eq!(result.get_low_winners(), &vec![0, 1]);
+    assert!(!result.is_scoop(0));
+    assert_eq!(result.split_pot(101), vec![76, 25]);
+    let (low, _) = result.get_players()[1].get_low().unwrap();
+    assert_eq!(low, &[7, 5, 3, 2, 1]);
+}
+
+#[test]
+fn no_low_when_the_board_has_fewer_than_three_low_cards() {
+    let board = cards("Kc Qd Jh 8s 2c");
+    assert!(omaha_low_score(&cards("Ac 3d 4s 5d"), &board).unwrap().is_none());
+    let result = omaha_hi_lo_showdown(&[cards("Ac 3d 4s 5d"), cards("Ah Kd 9d 9c")], &board).unwrap();
+    assert!(result.get_low_winners().is_empty());
+    assert_eq!(result.split_pot(100), vec![0, 100]);
+}
+
+#[test]
+fn omaha_rejects_wrong_hand_sizes() {
+    assert!(matches!(omaha_high_score(&cards("As Ks"), &cards("Ts 9s 2d")), Err(PokerError::InvalidHandSize { .. })));
+    assert!(matches!(omaha_high_score(&cards("As Ks Qs Js"), &cards("Ts 9s")), Err(PokerError::InvalidHandSize { .. })));
+}
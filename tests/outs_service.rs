@@ -0,0 +1,92 @@
+use poker_core::entity::hand::{Draw, HandCategory};
+use poker_core::error::PokerError;
+use poker_core::service::outs_service::{find_outs, find_outs_against, OutsResult};
+
+mod common;
+use common::cards;
+
+fn outs_making(result : &OutsResult, category : HandCategory) -> usize {
+    result.get_outs().iter().filter(|out| out.get_category() == category).count()
+}
+
+#[test]
+fn flush_draw_on_the_flop_counts_suit_and_overcard_outs() {
+    let result = find_outs(&cards("Ah Kh"), &cards("7h 2h 9c")).unwrap();
+    assert_eq!(result.get_category(), HandCategory::HighCard);
+    assert_eq!(result.get_draws(), &vec![Draw::FlushDraw]);
+    assert_eq!(outs_making(&result, HandCategory::Flush), 9);
+    //an ace or a king pairs a hole card, a seven, two or nine only pairs the board
+    assert_eq!(outs_making(&result, HandCategory::Pair), 6);
+    assert_eq!(result.get_outs().len(), 15);
+    assert_eq!(result.get_unseen(), 47);
+    assert_eq!(result.get_cards_to_come(), 2);
+    assert_eq!(result.get_rule_percent(), 60.0);
+    let exact = (1.0 - (32.0 / 47.0) * (31.0 / 46.0)) * 100.0;
+    assert!((result.get_exact_percent() - exact).abs() < 1e-9);
+}
+
+#[test]
+fn open_ended_on_the_turn_uses_the_rule_of_two() {
+    let result = find_outs(&cards("9s 8d"), &cards("7c 6h 2s Kd")).unwrap();
+    assert!(result.has_draw(Draw::OpenEnded));
+    assert!(!result.has_draw(Draw::Gutshot));
+    assert_eq!(outs_making(&result, HandCategory::Straight), 8);
+    assert_eq!(result.get_cards_to_come(), 1);
+    assert_eq!(result.get_rule_percent(), result.get_outs().len() as f64 * 2.0);
+    let exact = result.get_outs().len() as f64 * 100.0 / 46.0;
+    assert!((result.get_exact_percent() - exact).abs() < 1e-9);
+}
+
+#[test]
+fn gutshot_needs_one_number() {
+    let result = find_outs(&cards("9s 8d"), &cards("6c 5h Kd")).unwrap();
+    assert!(result.has_draw(Draw::Gutshot));
+    assert!(!result.has_draw(Draw::OpenEnded));
+    assert_eq!(outs_making(&result, HandCategory::Straight), 4);
+}
+
+#[test]
+fn flush_and_straight_draw_together_are_a_combo_draw() {
+    let result = find_outs(&cards("Jh Th"), &cards("9h 8c 2h")).unwrap();
+    assert!(result.has_draw(Draw::FlushDraw));
+    assert!(result.has_draw(Draw::OpenEnded));
+    assert!(result.has_draw(Draw::ComboDraw));
+    assert!(!result.has_draw(Draw::BackdoorFlush));
+    //the queen and seven of hearts make a straight and a flush, each out counts once as the flush
+    assert_eq!(outs_making(&result, HandCategory::Flush), 9);
+    assert_eq!(outs_making(&result, HandCategory::Straight), 6);
+    assert_eq!(outs_making(&result, HandCategory::Pair), 6);
+    assert_eq!(result.get_outs().len(), 21);
+}
+
+#[test]
+fn backdoor_draws_only_on_the_flop() {
+    let flop = find_outs(&cards("Ah Kh"), &cards("7h 2c 9s")).unwrap();
+    assert!(flop.has_draw(Draw::BackdoorFlush));
+    assert!(!flop.has_draw(Draw::FlushDraw));
+    assert!(!flop.has_draw(Draw::BackdoorStraight));
+    let flop = find_outs(&cards("9s 8d"), &cards("6c Kh 2s")).unwrap();
+    assert!(flop.has_draw(Draw::BackdoorStraight));
+    let turn = find_outs(&cards("Ah Kh"), &cards("7h 2c 9s 4d")).unwrap();
+    assert!(turn.get_draws().is_empty());
+}
+
+#[test]
+fn outs_against_an_opponent_must_take_the_lead() {
+    let result = find_outs_against(&cards("Ah Kd"), &cards("Qh 7c 2s"), &cards("Qs Jc")).unwrap();
+    assert_eq!(result.get_unseen(), 45);
+    assert_eq!(result.get_outs().len(), 6);
+    assert!(result.get_outs().iter().all(|out| out.get_category() == HandCategory::Pair));
+    let result = find_outs_against(&cards("Ah Kd"), &cards("Qh 7c 2s"), &cards("Qs Qc")).unwrap();
+    assert!(result.get_outs().is_empty());
+    assert_eq!(result.get_exact_percent(), 0.0);
+}
+
+#[test]
+fn outs_reject_bad_hands() {
+    assert!(matches!(find_outs(&cards("Ah Kh Qh"), &cards("7h 2h 9c")), Err(PokerError::InvalidHandSize { .. })));
+    assert!(matches!(find_outs(&cards("Ah Kh"), &cards("7h 2h")), Err(PokerError::InvalidHandSize { .. })));
+    assert!(matches!(find_outs(&cards("Ah Kh"), &cards("7h 2h 9c 4d 5s")), Err(PokerError::InvalidHandSize { .. })));
+    assert!(matches!(find_outs_against(&cards("Ah Kh"), &cards("7h 2h 9c"), &cards("Qs")), Err(PokerError::InvalidHandSize { .. })));
+    assert_eq!(find_outs_against(&cards("Ah Kh"), &cards("7h 2h 9c"), &cards("Ah Qc")).err(), Some(PokerError::DuplicateCard(String::from("Ah"))));
+}
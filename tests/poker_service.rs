@@ -0,0 +1,272 @@
+use std::cmp::Ordering;
+use std::rc::Rc;
+use poker_core::entity::card::{PokerCard, Type};
+use poker_core::entity::hand::HandCategory;
+use poker_core::entity::ruleset::{Ruleset, WildCards};
+use poker_core::error::PokerError;
+use poker_core::service::poker_service::{compare_score, hand_value_by_one_deck, IncrementalHand, score_by_cards, score_by_one_deck, score_by_one_deck_with_ruleset, score_partial, score_partial_with_ruleset, score_by_one_deck_with_wild_cards, showdown, showdown_with_ruleset, Match};
+
+mod common;
+use common::{cards, identities};
+
+#[test]
+fn showdown_ranks_players_and_picks_single_winner() {
+    let result = showdown(&[cards("As Ad"), cards("Kh Kc"), cards("7s 2d")], &cards("Ac Kd 9h 4s 3c")).unwrap();
+    assert_eq!(result.get_winners(), &vec![0]);
+    assert!(!result.is_split_pot());
+    let ranks = result.get_players().iter().map(|player| player.get_rank()).collect::<Vec<_>>();
+    assert_eq!(ranks, vec![1, 2, 3]);
+    assert_eq!(result.get_players()[0].get_category(), HandCategory::ThreeOfAKind);
+    assert!(result.get_players()[0].is_winner());
+}
+
+#[test]
+fn showdown_splits_pot_when_board_plays() {
+    let result = showdown(&[cards("2s 3d"), cards("4h 2c"), cards("Ah Kc")], &cards("Ts Js Qd Kd Ac")).unwrap();
+    assert_eq!(result.get_winners(), &vec![0, 1, 2]);
+    assert!(result.is_split_pot());
+    assert!(result.get_players().iter().all(|player| player.get_rank() == 1));
+}
+
+#[test]
+fn showdown_ties_on_kickers_and_shares_rank() {
+    //both play the ace kicker from the board, the third player is outkicked
+    let result = showdown(&[cards("Qs 3d"), cards("Qh 4c"), cards("Jd 9c")], &cards("Qd 8s 6h 2c Ac")).unwrap();
+    assert_eq!(result.get_winners(), &vec![0, 1]);
+    let ranks = result.get_players().iter().map(|player| player.get_rank()).collect::<Vec<_>>();
+    assert_eq!(ranks, vec![1, 1, 3]);
+}
+
+#[test]
+fn showdown_without_players_fails() {
+    assert!(showdown(&[], &cards("Qd 8s 6h 2c Ac")).is_err());
+}
+
+#[test]
+fn hand_values_order_by_category_then_kickers() {
+    let value = |notation : &str| hand_value_by_one_deck(&cards(notation)).unwrap();
+    let ordered = [
+        value("2s 4d 6h 8c Td"),
+        value("2s 2d 6h 8c Td"),
+        value("2s 2d 6h 6c Td"),
+        value("2s 2d 2h 8c Td"),
+        value("As 2d 3h 4c 5d"),
+        value("2s 4s 6s 8s Ts"),
+        value("2s 2d 2h Tc Td"),
+        value("2s 2d 2h 2c Td"),
+        value("5s 6s 7s 8s 9s"),
+        value("Ts Js Qs Ks As")
+    ];
+    for pair in ordered.windows(2) {
+        assert!(pair[0] < pair[1], "{:?} should lose to {:?}", pair[0].get_category(), pair[1].get_category());
+    }
+    assert!(HandCategory::Pair < HandCategory::TwoPair);
+    assert_eq!(value("Ks Kd 9h 4c 2d"), value("Kh Kc 9d 4s 2c"));
+    assert!(value("Ks Kd 9h 4c 2d") < value("Kh Kc 9d 5s 2c"));
+    assert_eq!(value("Ks Kd 9h 4c 2d").get_kickers(), &vec![13, 9, 4, 2]);
+}
+
+#[test]
+fn card_scores_hand_back_the_callers_aces() {
+    let hands = [
+        ("As Ks Qs Js Ts 2d 3c", HandCategory::RoyalFlush),
+        ("Ah Kd Qs Jc Ts 2d 3c", HandCategory::Straight),
+        ("Ad Jd 8d 5d 2d Kc 3h", HandCategory::Flush),
+        ("As 2d 3c 4h 5s Kd Kc", HandCategory::Straight)
+    ];
+    for (notation, expected) in hands {
+        let deck = identities(notation);
+        let (score, chosen, category) = score_by_cards(&deck).unwrap();
+        assert_eq!(category, expected, "{}", notation);
+        assert!(chosen.contains(&deck[0]), "{}", notation);
+        assert!(chosen.iter().all(|card| deck.contains(card)), "{}", notation);
+        assert_eq!(score, score_by_one_deck(&cards(notation)).unwrap().0, "{}", notation);
+    }
+}
+
+#[test]
+fn seeded_match_replays_the_same_deal() {
+    let deal = |seed : u64| {
+        let mut poker_match = Match::new_with_seed(seed);
+        assert_eq!(poker_match.get_seed(), Some(seed));
+        let player = poker_match.draw_player().unwrap();
+        let general = poker_match.draw_general().unwrap();
+        player.iter().chain(general.iter()).map(|card| card.to_string()).collect::<Vec<_>>()
+    };
+    assert_eq!(deal(42), deal(42));
+    assert_ne!(deal(42), deal(43));
+    let cards = deal(42);
+    let mut unique = cards.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), 7);
+}
+
+#[test]
+fn match_runs_out_of_cards() {
+    let mut poker_match = Match::new_with_seed(1);
+    assert_eq!(poker_match.draw(52).unwrap().len(), 52);
+    assert!(poker_match.draw(1).is_err());
+}
+
+#[test]
+fn short_deck_wheel_is_the_lowest_straight() {
+    let short_deck = Ruleset::ShortDeck { trips_beat_straight : false };
+    let (wheel, _, category) = score_by_one_deck_with_ruleset(&cards("As 6d 7c 8h 9s"), short_deck).unwrap();
+    assert_eq!(category, HandCategory::Straight);
+    let (six_high, _, _) = score_by_one_deck_with_ruleset(&cards("6d 7c 8h 9s Ts"), short_deck).unwrap();
+    assert!(compare_score(&six_high, &wheel).is_gt());
+    let (_, _, category) = score_by_one_deck_with_ruleset(&cards("As 6d 7c 8h 9s"), Ruleset::Standard).unwrap();
+    assert_eq!(category, HandCategory::HighCard);
+}
+
+#[test]
+fn short_deck_flush_beats_full_house() {
+    let board = cards("6h 9h Jh Jc 7d");
+    let result = showdown_with_ruleset(&[cards("Ah 8h"), cards("Js 9s")], &board, Ruleset::ShortDeck { trips_beat_straight : false }).unwrap();
+    assert_eq!(result.get_winners(), &vec![0]);
+    let result = showdown_with_ruleset(&[cards("Ah 8h"), cards("Js 9s")], &board, Ruleset::Standard).unwrap();
+    assert_eq!(result.get_winners(), &vec![1]);
+}
+
+#[test]
+fn short_deck_can_rank_trips_above_straights() {
+    let board = cards("Ts Jd Qc 6h 6d");
+    let hands = [cards("Ah Kh"), cards("6s 7c")];
+    let result = showdown_with_ruleset(&hands, &board, Ruleset::ShortDeck { trips_beat_straight : false }).unwrap();
+    assert_eq!(result.get_winners(), &vec![0]);
+    let result = showdown_with_ruleset(&hands, &board, Ruleset::ShortDeck { trips_beat_straight : true }).unwrap();
+    assert_eq!(result.get_winners(), &vec![1]);
+}
+
+#[test]
+fn short_deck_match_deals_from_36_cards() {
+    let mut poker_match = Match::new_with_ruleset(Ruleset::ShortDeck { trips_beat_straight : false }, 8);
+    assert_eq!(poker_match.get_deck().len(), 36);
+    let cards = poker_match.draw(36).unwrap();
+    assert!(cards.iter().all(|card| card.get_number() == 1 || card.get_number() >= 6));
+    assert!(poker_match.draw(1).is_err());
+}
+
+fn wild_category(notation : &str, wild_cards : &WildCards) -> HandCategory {
+    score_by_one_deck_with_wild_cards(&cards(notation), wild_cards, Ruleset::Standard).unwrap().2
+}
+
+#[test]
+fn wild_cards_make_five_of_a_kind_the_top_hand() {
+    let deuces = WildCards::new_deuces();
+    let (five_kings, chosen, category) = score_by_one_deck_with_wild_cards(&cards("2s 2h Ks Kd Kc"), &deuces, Ruleset::Standard).unwrap();
+    assert_eq!(category, HandCategory::FiveOfAKind);
+    assert!(chosen.iter().all(|card| card.get_number() == 13));
+    let (royal, _, _) = score_by_one_deck_with_wild_cards(&cards("As Ks Qs Js Ts"), &deuces, Ruleset::Standard).unwrap();
+    assert_eq!(compare_score(&five_kings, &royal), Ordering::Greater);
+
+    let jokers = WildCards::new(&[], false);
+    assert_eq!(wild_category("Xs Ah Ad Ac As", &jokers), HandCategory::FiveOfAKind);
+    assert_eq!(wild_category("Xs Kh Kd Qc 3s", &jokers), HandCategory::ThreeOfAKind);
+}
+
+#[test]
+fn wild_cards_fill_straights_and_flushes() {
+    let deuces = WildCards::new_deuces();
+    assert_eq!(wild_category("2c 5h 6h 8h 9h", &deuces), HandCategory::StraightFlush);
+    assert_eq!(wild_category("2c 2d Ah Kh Th", &deuces), HandCategory::RoyalFlush);
+    assert_eq!(wild_category("2c 5h 6d 8h 9s", &deuces), HandCategory::Straight);
+    assert_eq!(wild_category("2c 5h 9h Jh Kh", &deuces), HandCategory::Flush);
+}
+
+#[test]
+fn bug_only_plays_as_an_ace_or_in_a_straight_or_flush() {
+    let bug = WildCards::new_bug();
+    assert_eq!(wild_category("Xs Kh Kd Qc 3s", &bug), HandCategory::Pair);
+    assert_eq!(wild_category("Xs Ah Kd Qc 3s", &bug), HandCategory::Pair);
+    assert_eq!(wild_category("Xs Ah Ad Kc Ks", &bug), HandCategory::FullHouse);
+    assert_eq!(wild_category("Xs Kh Qd Jc Ts", &bug), HandCategory::Straight);
+    assert_eq!(wild_category("Xs 2h 5h 9h Jh", &bug), HandCategory::Flush);
+    let (pair_of_kings, chosen, _) = score_by_one_deck_with_wild_cards(&cards("Xs Kh Kd Qc 3s"), &bug, Ruleset::Standard).unwrap();
+    assert!(chosen.iter().any(|card| card.get_number() == 1));
+    let (natural, _, _) = score_by_one_deck_with_wild_cards(&cards("As Kh Kd Qc 3s"), &bug, Ruleset::Standard).unwrap();
+    assert_eq!(compare_score(&pair_of_kings, &natural), Ordering::Equal);
+}
+
+#[test]
+fn score_rejects_short_duplicate_and_invalid_decks() {
+    let short_deck = Ruleset::ShortDeck { trips_beat_straight : false };
+    assert_eq!(score_by_one_deck(&cards("As Kd Qc Jh")).unwrap_err(), PokerError::TooFewCards { minimum : 5, actual : 4 });
+
+    let mut duplicate = cards("As Kd Qc Jh");
+    duplicate.push(Rc::new(PokerCard::new_by_attribute(Type::SPADE, 1)));
+    assert_eq!(score_by_one_deck(&duplicate).unwrap_err(), PokerError::DuplicateCard(String::from("As")));
+
+    let mut high_ace = cards("Kd Qc Jh Th");
+    high_ace.push(Rc::new(PokerCard::new_by_attribute(Type::SPADE, 14)));
+    assert!(matches!(score_by_one_deck(&high_ace), Err(PokerError::InvalidCard(_))));
+
+    assert_eq!(score_by_one_deck(&cards("Xs Kd Qc Jh Th")).unwrap_err(), PokerError::InvalidCard(String::from("Xs")));
+    assert_eq!(score_by_one_deck_with_ruleset(&cards("Ah Kh Qh Jh 2h"), short_deck).unwrap_err(), PokerError::InvalidCard(String::from("2h")));
+    assert_eq!(score_by_one_deck_with_wild_cards(&cards("Xs Kh Qh Jh 5h"), &WildCards::new_bug(), short_deck).unwrap_err(), PokerError::InvalidCard(String::from("5h")));
+    assert!(score_by_one_deck_with_wild_cards(&cards("Xs Kh Qh Jh 6h"), &WildCards::new_bug(), short_deck).is_ok());
+}
+
+#[test]
+fn incremental_hand_agrees_with_score_on_every_street() {
+    let rulesets = [Ruleset::Standard, Ruleset::ShortDeck { trips_beat_straight : true }];
+    for ruleset in rulesets {
+        for seed in 0..300 {
+            let mut poker_match = Match::new_with_ruleset(ruleset, seed);
+            let dealt = poker_match.draw(7).unwrap().into_iter().map(Rc::new).collect::<Vec<_>>();
+            let mut hand = IncrementalHand::new_with_ruleset(ruleset);
+            for (index, card) in dealt.iter().enumerate() {
+                hand.add_card(Rc::clone(card)).unwrap();
+                let so_far = &dealt[..=index];
+                if so_far.len() < 5 {
+                    assert!(!hand.is_complete());
+                    let (score, _, category) = score_partial_with_ruleset(so_far, ruleset).unwrap();
+                    assert_eq!(hand.get_category(), Some(category));
+                    assert_eq!(compare_score(hand.get_score().unwrap(), &score), Ordering::Equal);
+                    continue;
+                }
+                let (score, chosen, category) = score_by_one_deck_with_ruleset(so_far, ruleset).unwrap();
+                assert_eq!(hand.get_category(), Some(category), "{:?}", so_far);
+                assert_eq!(compare_score(hand.get_score().unwrap(), &score), Ordering::Equal, "{:?}", so_far);
+                assert_eq!(hand.get_chosen().len(), 5);
+                let numbers = |cards : &[Rc<PokerCard>]| {
+                    let mut numbers = cards.iter().map(|card| card.get_poker_number()).collect::<Vec<_>>();
+                    numbers.sort();
+                    numbers
+                };
+                assert_eq!(numbers(hand.get_chosen()), numbers(&chosen), "{:?}", so_far);
+            }
+        }
+    }
+}
+
+#[test]
+fn incremental_hand_describes_partial_hands() {
+    let mut hand = IncrementalHand::new();
+    hand.add_cards(&cards("Kd 7c")).unwrap();
+    assert_eq!(hand.get_category(), Some(HandCategory::HighCard));
+    hand.add_card(cards("Ks")[0].clone()).unwrap();
+    assert_eq!(hand.get_category(), Some(HandCategory::Pair));
+    assert_eq!(score_partial(&cards("Kd 7c Ks")).unwrap().2, HandCategory::Pair);
+    hand.add_card(cards("7h")[0].clone()).unwrap();
+    assert_eq!(hand.get_category(), Some(HandCategory::TwoPair));
+    assert_eq!(compare_score(hand.get_score().unwrap(), &score_partial(&cards("Kd 7c Ks 7h")).unwrap().0), Ordering::Equal);
+    assert!(score_partial(&[]).is_err());
+    assert!(score_partial(&cards("Kd 7c Ks 7h 2s")).is_err());
+}
+
+#[test]
+fn incremental_hand_keeps_its_state_when_a_card_is_rejected() {
+    let mut hand = IncrementalHand::new();
+    hand.add_cards(&cards("As Kd")).unwrap();
+    assert_eq!(hand.add_cards(&cards("Qc As")).unwrap_err(), PokerError::DuplicateCard(String::from("As")));
+    assert_eq!(hand.get_cards().len(), 2);
+    assert!(hand.add_card(cards("Xh")[0].clone()).is_err());
+    hand.add_cards(&cards("Qc")).unwrap();
+    assert_eq!(hand.get_cards().len(), 3);
+
+    let mut short = IncrementalHand::new_with_ruleset(Ruleset::ShortDeck { trips_beat_straight : false });
+    assert_eq!(short.add_card(cards("2c")[0].clone()).unwrap_err(), PokerError::InvalidCard(String::from("2c")));
+    assert!(short.get_cards().is_empty());
+}
@@ -0,0 +1,72 @@
+use std::rc::Rc;
+use poker_core::entity::card::{PokerCard, PokerDeck};
+use poker_core::service::holdem_service::{Action, HoldemTable};
+use poker_core::service::poker_service::{score_by_one_deck, Match, PokerScore};
+use poker_core::service::pot_service::{build_pots, distribute_pots};
+
+fn score(cards : &str) -> Option<PokerScore> {
+    let deck = cards.parse::<PokerDeck>().unwrap().into_cards().into_iter().map(Rc::new).collect::<Vec<Rc<PokerCard>>>();
+    Some(score_by_one_deck(&deck).unwrap().0)
+}
+
+#[test]
+fn three_way_all_in_builds_main_and_side_pots() {
+    let pots = build_pots(&[100, 300, 500], &[false, false, false]);
+    assert_eq!(pots.len(), 3);
+    assert_eq!((pots[0].get_amount(), pots[0].get_eligible().clone()), (300, vec![0, 1, 2]));
+    assert_eq!((pots[1].get_amount(), pots[1].get_eligible().clone()), (400, vec![1, 2]));
+    assert_eq!((pots[2].get_amount(), pots[2].get_eligible().clone()), (200, vec![2]));
+
+    //shortest stack has the best hand, the biggest stack the worst
+    let scores = [score("As Ah Ad 2c 7h"), score("Ks Kh 9d 2d 7c"), score("Qs Jh 9c 4d 3c")];
+    assert_eq!(distribute_pots(&pots, &scores, 0), vec![300, 400, 200]);
+}
+
+#[test]
+fn folded_chips_stay_in_pot_without_eligibility() {
+    let pots = build_pots(&[50, 200, 200, 120], &[true, false, false, true]);
+    assert_eq!(pots.len(), 1);
+    assert_eq!(pots[0].get_amount(), 570);
+    assert_eq!(pots[0].get_eligible(), &vec![1, 2]);
+
+    let scores = [None, score("2s 2h 5d 9c Jh"), score("3s 3h 5c 9d Jd"), None];
+    assert_eq!(distribute_pots(&pots, &scores, 0), vec![0, 0, 570, 0]);
+}
+
+#[test]
+fn tie_splits_pot_and_odd_chip_goes_left_of_button() {
+    let pots = build_pots(&[50, 50, 50, 1], &[false, false, false, true]);
+    assert_eq!(pots[0].get_amount(), 151);
+
+    //seats 0 and 2 hold the same straight on a shared board
+    let scores = [score("9s 8h 7d 6c 5h"), score("As Ah 4d 3c Kh"), score("9h 8d 7c 6s 5c"), None];
+    assert_eq!(distribute_pots(&pots, &scores, 1), vec![75, 0, 76, 0]);
+    assert_eq!(distribute_pots(&pots, &scores, 2), vec![76, 0, 75, 0]);
+}
+
+#[test]
+fn multi_way_all_in_with_tie_in_side_pot() {
+    let pots = build_pots(&[100, 300, 300, 60], &[false, false, false, false]);
+    assert_eq!(pots.len(), 3);
+    assert_eq!((pots[0].get_amount(), pots[0].get_eligible().clone()), (240, vec![0, 1, 2, 3]));
+    assert_eq!((pots[1].get_amount(), pots[1].get_eligible().clone()), (120, vec![0, 1, 2]));
+    assert_eq!((pots[2].get_amount(), pots[2].get_eligible().clone()), (400, vec![1, 2]));
+
+    //seat 0 wins what it can reach, seats 1 and 2 chop the rest, seat 3 loses
+    let scores = [score("Ks Kh Kd 2c 7h"), score("Qs Qh 9d 2d 7c"), score("Qd Qc 9s 2h 7d"), score("Js Th 9c 4d 3c")];
+    assert_eq!(distribute_pots(&pots, &scores, 3), vec![360, 200, 200, 0]);
+}
+
+#[test]
+fn table_settles_side_pots_and_keeps_chips() {
+    let mut table = HoldemTable::new(vec![1000, 300, 100], 5, 10);
+    table.start_hand(Match::new_with_seed(11)).unwrap();
+    while let Some(seat) = table.get_to_act() {
+        table.act(seat, Action::AllIn).unwrap();
+    }
+    let settlement = table.get_settlement().unwrap();
+    assert!(settlement.is_shown_down());
+    assert_eq!(settlement.get_pots().iter().map(|pot| pot.get_amount()).sum::<u64>(), 700);
+    assert_eq!(table.get_seats().iter().map(|seat| seat.get_stack()).sum::<u64>(), 1400);
+    assert!(table.get_seats()[0].get_stack() >= 700);
+}
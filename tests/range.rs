@@ -0,0 +1,57 @@
+use poker_core::entity::range::{HandRange, ParseRangeError};
+
+fn combo_names(range : &HandRange) -> Vec<String> {
+    let mut names = range.get_combos().iter()
+        .map(|combo| format!("{}{}", combo.get_cards()[0], combo.get_cards()[1]))
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+#[test]
+fn pair_plus_expands_to_every_higher_pair() {
+    let range = "TT+".parse::<HandRange>().unwrap();
+    assert_eq!(range.get_combos().len(), 5 * 6);
+    assert_eq!(range.get_total_weight(), 30.0);
+    assert_eq!("99+".parse::<HandRange>().unwrap().get_combos().len(), 6 * 6);
+}
+
+#[test]
+fn suited_span_expands_each_kicker() {
+    let range = "A5s-A2s".parse::<HandRange>().unwrap();
+    assert_eq!(range.get_combos().len(), 4 * 4);
+    assert!(range.get_combos().iter().all(|combo| combo.get_cards()[0].get_card_type() == combo.get_cards()[1].get_card_type()));
+    assert_eq!(combo_names(&range), combo_names(&"A2s-A5s".parse::<HandRange>().unwrap()));
+    assert_eq!("ATo+".parse::<HandRange>().unwrap().get_combos().len(), 4 * 12);
+    assert_eq!("AK".parse::<HandRange>().unwrap().get_combos().len(), 16);
+}
+
+#[test]
+fn weights_apply_per_token_and_last_listing_wins() {
+    let range = "KK:0.5, AKs, 76s:0.25".parse::<HandRange>().unwrap();
+    assert_eq!(range.get_combos().len(), 6 + 4 + 4);
+    assert_eq!(range.get_total_weight(), 3.0 + 4.0 + 1.0);
+    let range = "AA, AsAh:0.5".parse::<HandRange>().unwrap();
+    assert_eq!(range.get_combos().len(), 6);
+    assert_eq!(range.get_total_weight(), 5.5);
+    assert_eq!("AA, AsAh:0".parse::<HandRange>().unwrap().get_combos().len(), 5);
+}
+
+#[test]
+fn malformed_ranges_are_rejected() {
+    assert!(matches!("TT:2".parse::<HandRange>(), Err(ParseRangeError::InvalidWeight(_))));
+    assert!(matches!("AK:x".parse::<HandRange>(), Err(ParseRangeError::InvalidWeight(_))));
+    assert!(matches!("A5s-K2s".parse::<HandRange>(), Err(ParseRangeError::InvalidSpan(_))));
+    assert!(matches!("A5s-A2o".parse::<HandRange>(), Err(ParseRangeError::InvalidSpan(_))));
+    assert!(matches!("AAs".parse::<HandRange>(), Err(ParseRangeError::InvalidHand(_))));
+    assert!(matches!("A1".parse::<HandRange>(), Err(ParseRangeError::InvalidHand(_))));
+    assert!(matches!("AKx".parse::<HandRange>(), Err(ParseRangeError::InvalidHand(_))));
+}
+
+#[test]
+fn jokers_are_not_a_range_rank() {
+    assert!(matches!("XX".parse::<HandRange>(), Err(ParseRangeError::InvalidHand(_))));
+    assert!(matches!("AXs".parse::<HandRange>(), Err(ParseRangeError::InvalidHand(_))));
+    assert!(matches!("X2+".parse::<HandRange>(), Err(ParseRangeError::InvalidHand(_))));
+    assert!(matches!("XsAh".parse::<HandRange>(), Err(ParseRangeError::InvalidHand(_))));
+}
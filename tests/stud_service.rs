@@ -0,0 +1,87 @@
+use poker_core::entity::card::Type;
+use poker_core::error::{ActionError, PokerError};
+use poker_core::service::holdem_service::Action;
+use poker_core::service::poker_service::Match;
+use poker_core::service::stud_service::{StudGame, StudStreet, StudTable};
+
+fn suit_rank(card_type : Type) -> i16 {
+    match card_type {
+        Type::CLUB => 0,
+        Type::DIAMOND => 1,
+        Type::HEART => 2,
+        Type::SPADE => 3
+    }
+}
+
+//ante 1, bring-in 2, limits 5/10
+fn start(game : StudGame, seed : u64) -> StudTable {
+    let mut table = StudTable::new(game, vec![100; 4], 1, 2, 5, 10);
+    table.start_hand(Match::new_with_seed(seed)).unwrap();
+    table
+}
+
+#[test]
+fn lowest_up_card_brings_in_and_suits_break_ties() {
+    for seed in 0..20 {
+        let table = start(StudGame::SevenCardStud, seed);
+        let keys = table.get_seats().iter()
+            .map(|seat| (seat.get_up_cards()[0].get_poker_number(), suit_rank(seat.get_up_cards()[0].get_card_type())))
+            .collect::<Vec<_>>();
+        let lowest = (0..4).min_by_key(|&index| keys[index]).unwrap();
+        assert_eq!(table.get_bring_in_seat(), Some(lowest));
+        assert_eq!(table.get_seats()[lowest].get_street_bet(), 2);
+        assert_eq!(table.get_current_bet(), 2);
+        assert_eq!(table.get_to_act(), Some((lowest + 1) % 4));
+        assert_eq!(table.get_pot(), 4 + 2);
+    }
+}
+
+#[test]
+fn razz_brings_in_the_highest_up_card() {
+    for seed in 0..20 {
+        let table = start(StudGame::Razz, seed);
+        let keys = table.get_seats().iter()
+            .map(|seat| (seat.get_up_cards()[0].get_number(), suit_rank(seat.get_up_cards()[0].get_card_type())))
+            .collect::<Vec<_>>();
+        let highest = (0..4).max_by_key(|&index| keys[index]).unwrap();
+        assert_eq!(table.get_bring_in_seat(), Some(highest));
+    }
+}
+
+#[test]
+fn bring_in_is_completed_to_the_small_bet() {
+    let mut table = start(StudGame::SevenCardStud, 4);
+    let first = table.get_to_act().unwrap();
+    assert_eq!(table.get_raise_to(), 5);
+    assert_eq!(table.act(first, Action::Raise(7)), Err(PokerError::IllegalAction(ActionError::WrongBetSize { expected : 5 })));
+    assert_eq!(table.act(first, Action::Bet(5)), Err(PokerError::IllegalAction(ActionError::CannotBet)));
+    table.act(first, Action::Raise(5)).unwrap();
+    assert_eq!(table.get_current_bet(), 5);
+    assert_eq!(table.get_raise_to(), 10);
+}
+
+#[test]
+fn calling_the_bring_in_around_closes_third_street() {
+    let mut table = start(StudGame::SevenCardStud, 9);
+    let bring_in = table.get_bring_in_seat().unwrap();
+    for _ in 0..3 {
+        let seat = table.get_to_act().unwrap();
+        assert_ne!(seat, bring_in);
+        table.act(seat, Action::Call).unwrap();
+    }
+    assert_eq!(table.get_street(), StudStreet::Fourth);
+    assert_eq!(table.get_pot(), 4 + 4 * 2);
+    assert!(table.get_seats().iter().all(|seat| seat.get_up_cards().len() == 2));
+}
+
+#[test]
+fn bets_are_capped_at_a_bet_and_three_raises() {
+    let mut table = start(StudGame::SevenCardStud, 2);
+    for amount in [5, 10, 15, 20] {
+        let seat = table.get_to_act().unwrap();
+        table.act(seat, Action::Raise(amount)).unwrap();
+    }
+    let seat = table.get_to_act().unwrap();
+    assert_eq!(table.act(seat, Action::Raise(25)), Err(PokerError::IllegalAction(ActionError::CannotRaise)));
+    table.act(seat, Action::Call).unwrap();
+}
@@ -0,0 +1,67 @@
+use poker_core::error::PokerError;
+use poker_core::service::tien_len_service::{TienLenCombination, TienLenError, TienLenGame, TienLenMove};
+
+mod common;
+use common::solitaire_cards;
+
+fn play(notation : &str) -> TienLenMove {
+    TienLenMove::new(&solitaire_cards(notation)).unwrap()
+}
+
+#[test]
+fn moves_are_classified() {
+    assert_eq!(play("7h").get_combination(), TienLenCombination::Single);
+    assert_eq!(play("9s 9d 9c 9h").get_combination(), TienLenCombination::FourOfAKind);
+    assert_eq!(play("Jd Qs Kc Ah").get_combination(), TienLenCombination::Sequence(4));
+    assert_eq!(play("3s 3c 4d 4h 5s 5c").get_combination(), TienLenCombination::ConsecutivePairs(3));
+    assert_eq!(TienLenMove::new(&solitaire_cards("Qs Kc Ah 2d")), Err(PokerError::TienLen(TienLenError::InvalidCombination)));
+    assert_eq!(TienLenMove::new(&solitaire_cards("Ks Kc Ad Ah 2s 2c")), Err(PokerError::TienLen(TienLenError::InvalidCombination)));
+}
+
+#[test]
+fn same_combinations_compare_on_the_top_card() {
+    assert!(play("5s 5h").beats(&play("5c 5d")));
+    assert!(!play("5c 5d").beats(&play("5s 5h")));
+    assert!(play("2s").beats(&play("Ah")));
+    assert!(!play("4s 5d 6c").beats(&play("Ts Jd Qc Kh")));
+}
+
+#[test]
+fn chops_beat_twos_and_each_other() {
+    let three_pairs = play("3s 3c 4d 4h 5s 5c");
+    let four_of_a_kind = play("9s 9d 9c 9h");
+    let four_pairs = play("6s 6c 7d 7h 8s 8c 9s 9c");
+    let two = play("2h");
+    let pair_of_twos = play("2s 2d");
+    assert!(three_pairs.beats(&two));
+    assert!(!three_pairs.beats(&pair_of_twos));
+    assert!(!three_pairs.beats(&play("Ah")));
+    assert!(four_of_a_kind.beats(&two));
+    assert!(four_of_a_kind.beats(&pair_of_twos));
+    assert!(four_of_a_kind.beats(&three_pairs));
+    assert!(!four_of_a_kind.beats(&play("Ks Kd")));
+    assert!(four_pairs.beats(&pair_of_twos));
+    assert!(four_pairs.beats(&four_of_a_kind));
+    assert!(four_pairs.beats(&three_pairs));
+    assert!(!three_pairs.beats(&four_of_a_kind));
+}
+
+#[test]
+fn a_chop_on_a_two_takes_the_round() {
+    let mut game = TienLenGame::new_with_hands(vec![solitaire_cards("3s 2h Kd"), solitaire_cards("9d 4s 4c 5d 5h 6s 6c 7c")]).unwrap();
+    assert_eq!(game.get_to_act(), Some(0));
+    assert_eq!(game.play(0, &solitaire_cards("Kd")), Err(PokerError::TienLen(TienLenError::MustPlayLowestCard)));
+    game.play(0, &solitaire_cards("3s")).unwrap();
+    game.play(1, &solitaire_cards("9d")).unwrap();
+    game.play(0, &solitaire_cards("2h")).unwrap();
+    assert_eq!(game.play(1, &solitaire_cards("7c")), Err(PokerError::TienLen(TienLenError::CannotBeat)));
+    game.play(1, &solitaire_cards("4s 4c 5d 5h 6s 6c")).unwrap();
+    assert_eq!(game.play(0, &solitaire_cards("Kd")), Err(PokerError::TienLen(TienLenError::CannotBeat)));
+    game.pass(0).unwrap();
+    assert!(game.get_last_move().is_none());
+    assert_eq!(game.get_to_act(), Some(1));
+    game.play(1, &solitaire_cards("7c")).unwrap();
+    assert!(game.is_game_over());
+    assert_eq!(game.get_winner(), Some(1));
+    assert_eq!(game.get_finished(), &vec![1, 0]);
+}